@@ -17,7 +17,9 @@ use embedder_traits::Cursor;
 use euclid::{TypedPoint2D, TypedScale, TypedVector2D};
 use gfx_traits::Epoch;
 #[cfg(feature = "gl")]
-use image::{DynamicImage, ImageFormat};
+use image::pnm::{PNMSubtype, SampleEncoding};
+#[cfg(feature = "gl")]
+use image::{DynamicImage, ImageOutputFormat};
 use ipc_channel::ipc;
 use libc::c_void;
 use msg::constellation_msg::{PipelineId, PipelineIndex, PipelineNamespaceId};
@@ -32,6 +34,7 @@ use script_traits::{
     MouseButton, MouseEventType, ScrollState, TouchEventType, TouchId, WheelDelta,
 };
 use script_traits::{UntrustedNodeAddress, WindowSizeData, WindowSizeType};
+use servo_config::opts::{OutputColorSpace, OutputFormat};
 use servo_geometry::DeviceIndependentPixel;
 use std::collections::HashMap;
 use std::env;
@@ -197,6 +200,15 @@ pub struct IOCompositor<Window: WindowMethods + ?Sized> {
 
     output_file: Option<String>,
 
+    /// The image format that `output_file` is written in.
+    output_format: OutputFormat,
+
+    /// The color space that `output_file` is encoded in.
+    output_color_space: OutputColorSpace,
+
+    /// The RGBA color that the viewport is cleared to before painting, if not white.
+    background_color: Option<[f32; 4]>,
+
     is_running_problem_test: bool,
 
     /// True to exit after page load ('-x').
@@ -276,10 +288,14 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
         window: Rc<Window>,
         state: InitialCompositorState,
         output_file: Option<String>,
+        output_format: OutputFormat,
+        output_color_space: OutputColorSpace,
+        background_color: Option<[f32; 4]>,
         is_running_problem_test: bool,
         exit_after_load: bool,
         convert_mouse_to_touch: bool,
         device_pixels_per_px: Option<f32>,
+        initial_zoom: f32,
     ) -> Self {
         let composite_target = match output_file {
             Some(_) => CompositeTarget::PngFile,
@@ -300,7 +316,7 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
             composite_target,
             shutdown_state: ShutdownState::NotShuttingDown,
             page_zoom: TypedScale::new(1.0),
-            viewport_zoom: PinchZoomFactor::new(1.0),
+            viewport_zoom: PinchZoomFactor::new(initial_zoom),
             min_viewport_zoom: None,
             max_viewport_zoom: None,
             zoom_action: false,
@@ -317,6 +333,9 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
             pending_paint_metrics: HashMap::new(),
             cursor: Cursor::None,
             output_file,
+            output_format,
+            output_color_space,
+            background_color,
             is_running_problem_test,
             exit_after_load,
             convert_mouse_to_touch,
@@ -328,24 +347,41 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
         window: Rc<Window>,
         state: InitialCompositorState,
         output_file: Option<String>,
+        output_format: OutputFormat,
+        output_color_space: OutputColorSpace,
+        background_color: Option<[f32; 4]>,
         is_running_problem_test: bool,
         exit_after_load: bool,
         convert_mouse_to_touch: bool,
         device_pixels_per_px: Option<f32>,
+        initial_zoom: f32,
     ) -> Self {
         let mut compositor = IOCompositor::new(
             window,
             state,
             output_file,
+            output_format,
+            output_color_space,
+            background_color,
             is_running_problem_test,
             exit_after_load,
             convert_mouse_to_touch,
             device_pixels_per_px,
+            initial_zoom,
         );
 
         // Set the size of the root layer.
         compositor.update_zoom_transform();
 
+        // Start at the pinch zoom given with `--zoom`.
+        if initial_zoom != 1.0 {
+            let mut txn = webrender_api::Transaction::new();
+            txn.set_pinch_zoom(webrender_api::ZoomFactor::new(initial_zoom));
+            compositor
+                .webrender_api
+                .send_transaction(compositor.webrender_document, txn);
+        }
+
         // Tell the constellation about the initial window size.
         compositor.send_window_size(WindowSizeType::Initial);
 
@@ -1341,12 +1377,24 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
                     None,
                     self.time_profiler_chan.clone(),
                     || match self.output_file.as_ref() {
-                        Some(path) => match File::create(path) {
+                        // The file may be under an --output-dir that doesn't exist yet.
+                        Some(path) => match std::path::Path::new(path)
+                            .parent()
+                            .map_or(Ok(()), create_dir_all)
+                            .and_then(|()| File::create(path))
+                        {
                             Ok(mut file) => {
-                                let img = gl::draw_img(gl, rt_info, width, height);
+                                let mut img = gl::draw_img(gl, rt_info, width, height);
+                                gl::convert_color_space(&mut img, self.output_color_space);
                                 let dynamic_image = DynamicImage::ImageRgb8(img);
-                                if let Err(e) = dynamic_image.write_to(&mut file, ImageFormat::PNG)
-                                {
+                                let format = match self.output_format {
+                                    OutputFormat::Png => ImageOutputFormat::PNG,
+                                    OutputFormat::Bmp => ImageOutputFormat::BMP,
+                                    OutputFormat::Ppm => ImageOutputFormat::PNM(
+                                        PNMSubtype::Pixmap(SampleEncoding::Binary),
+                                    ),
+                                };
+                                if let Err(e) = dynamic_image.write_to(&mut file, format) {
                                     error!("Failed to save {} ({}).", path, e);
                                 }
                             },
@@ -1395,7 +1443,7 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
         gl.clear_color(0.0, 0.0, 0.0, 0.0);
         gl.clear(gleam::gl::COLOR_BUFFER_BIT);
 
-        // Make the viewport white.
+        // Make the viewport white, or the requested background color.
         let viewport = self.embedder_coordinates.get_flipped_viewport();
         gl.scissor(
             viewport.origin.x,
@@ -1403,7 +1451,8 @@ impl<Window: WindowMethods + ?Sized> IOCompositor<Window> {
             viewport.size.width,
             viewport.size.height,
         );
-        gl.clear_color(1.0, 1.0, 1.0, 1.0);
+        let [red, green, blue, alpha] = self.background_color.unwrap_or([1.0, 1.0, 1.0, 1.0]);
+        gl.clear_color(red, green, blue, alpha);
         gl.enable(gleam::gl::SCISSOR_TEST);
         gl.clear(gleam::gl::COLOR_BUFFER_BIT);
         gl.disable(gleam::gl::SCISSOR_TEST);
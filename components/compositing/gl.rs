@@ -4,6 +4,7 @@
 
 use gleam::gl;
 use image::RgbImage;
+use servo_config::opts::OutputColorSpace;
 use servo_geometry::FramebufferUintLength;
 
 #[derive(Default)]
@@ -122,3 +123,55 @@ pub fn draw_img(
 
     RgbImage::from_raw(width as u32, height as u32, pixels).expect("Flipping image failed!")
 }
+
+/// Converts linear sRGB to linear Display P3; both use the D65 white point.
+const SRGB_TO_DISPLAY_P3: [[f32; 3]; 3] = [
+    [0.8224621, 0.1775380, 0.0000000],
+    [0.0331941, 0.9668058, 0.0000000],
+    [0.0170827, 0.0723974, 0.9105199],
+];
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Re-encode an image read back from the framebuffer, which is always sRGB, in `color_space`.
+pub fn convert_color_space(img: &mut RgbImage, color_space: OutputColorSpace) {
+    if color_space == OutputColorSpace::Srgb {
+        return;
+    }
+
+    for pixel in img.pixels_mut() {
+        let mut linear = [0.0; 3];
+        for (channel, value) in linear.iter_mut().zip(pixel.data.iter()) {
+            *channel = srgb_to_linear(*value as f32 / 255.0);
+        }
+        let encoded = match color_space {
+            OutputColorSpace::Srgb => unreachable!(),
+            OutputColorSpace::Linear => linear,
+            OutputColorSpace::DisplayP3 => {
+                let mut p3 = [0.0; 3];
+                for (channel, row) in p3.iter_mut().zip(SRGB_TO_DISPLAY_P3.iter()) {
+                    let value = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+                    *channel = linear_to_srgb(value);
+                }
+                p3
+            },
+        };
+        for (value, channel) in pixel.data.iter_mut().zip(encoded.iter()) {
+            *value = (channel.max(0.0).min(1.0) * 255.0).round() as u8;
+        }
+    }
+}
@@ -15,7 +15,7 @@ use std::path::PathBuf;
     not(target_os = "android")
 ))]
 pub fn default_config_dir() -> Option<PathBuf> {
-    let mut config_dir = ::dirs::config_dir().unwrap();
+    let mut config_dir = ::dirs::config_dir()?;
     config_dir.push("servo");
     config_dir.push("default");
     Some(config_dir)
@@ -30,14 +30,14 @@ pub fn default_config_dir() -> Option<PathBuf> {
 pub fn default_config_dir() -> Option<PathBuf> {
     // FIXME: use `config_dir()` ($HOME/Library/Preferences)
     // instead of `data_dir()` ($HOME/Library/Application Support) ?
-    let mut config_dir = ::dirs::data_dir().unwrap();
+    let mut config_dir = ::dirs::data_dir()?;
     config_dir.push("Servo");
     Some(config_dir)
 }
 
 #[cfg(all(target_os = "windows", not(feature = "uwp")))]
 pub fn default_config_dir() -> Option<PathBuf> {
-    let mut config_dir = ::dirs::config_dir().unwrap();
+    let mut config_dir = ::dirs::config_dir()?;
     config_dir.push("Servo");
     Some(config_dir)
 }
@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::env;
+use std::process::Command;
+
+// Record the compiler and target for `--version-json`.
+fn main() {
+    let target = env::var("TARGET").unwrap();
+    println!("cargo:rustc-env=SERVO_BUILD_TARGET={}", target);
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_owned())
+        .unwrap_or_else(|| String::from("unknown"));
+    println!(
+        "cargo:rustc-env=SERVO_BUILD_RUSTC_VERSION={}",
+        rustc_version
+    );
+}
@@ -14,6 +14,8 @@ pub enum PrefValue {
     Int(i64),
     Str(String),
     Bool(bool),
+    Array(Vec<PrefValue>),
+    Object(HashMap<String, PrefValue>),
     Missing,
 }
 
@@ -50,6 +52,14 @@ impl PrefValue {
         }
     }
 
+    pub fn as_array(&self) -> Option<&[PrefValue]> {
+        if let PrefValue::Array(val) = self {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
     pub fn is_missing(&self) -> bool {
         match self {
             PrefValue::Missing => true,
@@ -63,9 +73,37 @@ impl PrefValue {
             Value::Number(n) if n.is_i64() => Some(PrefValue::Int(n.as_i64().unwrap())),
             Value::Number(n) if n.is_f64() => Some(PrefValue::Float(n.as_f64().unwrap())),
             Value::String(s) => Some(PrefValue::Str(s.to_owned())),
+            Value::Array(values) => values
+                .iter()
+                .map(PrefValue::from_json_value)
+                .collect::<Option<Vec<_>>>()
+                .map(PrefValue::Array),
+            Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| PrefValue::from_json_value(value).map(|v| (key.clone(), v)))
+                .collect::<Option<HashMap<_, _>>>()
+                .map(PrefValue::Object),
             _ => None,
         }
     }
+
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            PrefValue::Float(f) => Value::from(*f),
+            PrefValue::Int(i) => Value::from(*i),
+            PrefValue::Str(s) => Value::from(s.clone()),
+            PrefValue::Bool(b) => Value::from(*b),
+            PrefValue::Array(values) => {
+                Value::Array(values.iter().map(PrefValue::to_json_value).collect())
+            },
+            PrefValue::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.to_json_value()))
+                    .collect(),
+            ),
+            PrefValue::Missing => Value::Null,
+        }
+    }
 }
 
 impl FromStr for PrefValue {
@@ -170,6 +208,17 @@ impl fmt::Display for PrefError {
 
 impl std::error::Error for PrefError {}
 
+/// Where the current value of a preference came from, as reported by `Preferences::origin`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrefOrigin {
+    /// The built-in default, or a value set at runtime.
+    Default,
+    /// A user `prefs.json` or a `--prefs-file`.
+    UserFile,
+    /// A `--pref` command line argument.
+    CommandLine,
+}
+
 pub struct Accessor<P, V> {
     pub getter: Box<dyn Fn(&P) -> V + Sync>,
     pub setter: Box<dyn Fn(&mut P, V) + Sync>,
@@ -192,6 +241,7 @@ pub struct Preferences<'m, P> {
     user_prefs: Arc<RwLock<P>>,
     default_prefs: P,
     accessors: &'m HashMap<String, Accessor<P, PrefValue>>,
+    origins: RwLock<HashMap<String, PrefOrigin>>,
 }
 
 impl<'m, P: Clone> Preferences<'m, P> {
@@ -202,6 +252,7 @@ impl<'m, P: Clone> Preferences<'m, P> {
             user_prefs: Arc::new(RwLock::new(default_prefs.clone())),
             default_prefs,
             accessors,
+            origins: RwLock::new(HashMap::new()),
         }
     }
 
@@ -220,6 +271,30 @@ impl<'m, P: Clone> Preferences<'m, P> {
         }
     }
 
+    /// Retrieve a boolean preference, or `None` if it is missing or not a boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).as_bool()
+    }
+
+    /// Retrieve an integer preference, or `None` if it is missing or not an integer.
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.get(key).as_i64()
+    }
+
+    /// Retrieve a float preference, or `None` if it is missing or not a float.
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.get(key).as_f64()
+    }
+
+    /// Retrieve a string preference, or `None` if it is missing or not a string.
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        if let PrefValue::Str(val) = self.get(key) {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
     /// Creates an iterator over all keys and values
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = (String, PrefValue)> + 'a {
         let prefs = self.user_prefs.read().unwrap();
@@ -233,6 +308,16 @@ impl<'m, P: Clone> Preferences<'m, P> {
         self.accessors.keys().map(String::as_str)
     }
 
+    /// All keys starting with `prefix` and their values, sorted by key.
+    pub fn get_all_matching(&self, prefix: &str) -> Vec<(String, PrefValue)> {
+        let mut matching: Vec<(String, PrefValue)> = self
+            .iter()
+            .filter(|&(ref key, _)| key.starts_with(prefix))
+            .collect();
+        matching.sort_by(|a, b| a.0.cmp(&b.0));
+        matching
+    }
+
     fn set_inner<V>(&self, key: &str, mut prefs: &mut P, val: V) -> Result<(), PrefError>
     where
         V: Into<PrefValue>,
@@ -244,32 +329,83 @@ impl<'m, P: Clone> Preferences<'m, P> {
         }
     }
 
+    fn set_origin(&self, key: &str, origin: PrefOrigin) {
+        let mut origins = self.origins.write().unwrap();
+        if origin == PrefOrigin::Default {
+            origins.remove(key);
+        } else {
+            origins.insert(String::from(key), origin);
+        }
+    }
+
     /// Set a new value for a preference, using its key.
     pub fn set<V>(&self, key: &str, val: V) -> Result<(), PrefError>
+    where
+        V: Into<PrefValue>,
+    {
+        self.set_from(key, val, PrefOrigin::Default)
+    }
+
+    /// Set a new value for a preference and record that it came from `origin`.
+    pub fn set_from<V>(&self, key: &str, val: V, origin: PrefOrigin) -> Result<(), PrefError>
     where
         V: Into<PrefValue>,
     {
         let mut prefs = self.user_prefs.write().unwrap();
-        self.set_inner(key, &mut prefs, val)
+        self.set_inner(key, &mut prefs, val)?;
+        self.set_origin(key, origin);
+        Ok(())
     }
 
     pub fn set_all<M>(&self, values: M) -> Result<(), PrefError>
+    where
+        M: IntoIterator<Item = (String, PrefValue)>,
+    {
+        self.set_all_from(values, PrefOrigin::Default)
+    }
+
+    /// Set several preferences that came from the same place, as with `set_from`.
+    pub fn set_all_from<M>(&self, values: M, origin: PrefOrigin) -> Result<(), PrefError>
     where
         M: IntoIterator<Item = (String, PrefValue)>,
     {
         let mut prefs = self.user_prefs.write().unwrap();
         for (k, v) in values.into_iter() {
             self.set_inner(&k, &mut prefs, v)?;
+            self.set_origin(&k, origin);
         }
         Ok(())
     }
 
+    /// Where the current value of a preference came from, or `None` if there is no such
+    /// preference. Values written through the `values` lock directly, as `set_pref!` does, keep
+    /// the origin of the value they replaced.
+    pub fn origin(&self, key: &str) -> Option<PrefOrigin> {
+        if !self.accessors.contains_key(key) {
+            return None;
+        }
+        let origins = self.origins.read().unwrap();
+        Some(origins.get(key).cloned().unwrap_or(PrefOrigin::Default))
+    }
+
+    /// The origin of every preference that wasn't set from its default.
+    pub fn origins(&self) -> HashMap<String, PrefOrigin> {
+        self.origins.read().unwrap().clone()
+    }
+
+    /// Replace the origins of all preferences; any preference missing from `origins` is taken to
+    /// have its default origin.
+    pub fn set_origins(&self, origins: HashMap<String, PrefOrigin>) {
+        *self.origins.write().unwrap() = origins;
+    }
+
     pub fn reset(&self, key: &str) -> Result<PrefValue, PrefError> {
         if let Some(accessor) = self.accessors.get(key) {
             let mut prefs = self.user_prefs.write().unwrap();
             let old_pref = (accessor.getter)(&prefs);
             let default_pref = (accessor.getter)(&self.default_prefs);
             (accessor.setter)(&mut prefs, default_pref);
+            self.set_origin(key, PrefOrigin::Default);
             Ok(old_pref)
         } else {
             Err(PrefError::NoSuchPref(String::from(key)))
@@ -278,5 +414,6 @@ impl<'m, P: Clone> Preferences<'m, P> {
 
     pub fn reset_all(&self) {
         *self.user_prefs.write().unwrap() = self.default_prefs.clone();
+        self.origins.write().unwrap().clear();
     }
 }
@@ -13,7 +13,7 @@ use std::io::{stderr, Read, Write};
 use std::path::PathBuf;
 
 use crate::pref_util::Preferences;
-pub use crate::pref_util::{PrefError, PrefValue};
+pub use crate::pref_util::{PrefError, PrefOrigin, PrefValue};
 use gen::Prefs;
 
 lazy_static! {
@@ -58,15 +58,41 @@ pub fn pref_map() -> &'static Preferences<'static, Prefs> {
     &PREFS
 }
 
-pub(crate) fn add_user_prefs() {
-    if let Some(path) = user_prefs_path() {
+/// Where the current value of the preference named `key` came from, or `None` if there is no
+/// such preference.
+pub fn origin_of(key: &str) -> Option<PrefOrigin> {
+    PREFS.origin(key)
+}
+
+/// A copy of every preference value and its origin, taken with `snapshot` and put back with
+/// `restore`.
+pub struct PrefsSnapshot(Prefs, HashMap<String, PrefOrigin>);
+
+/// Capture the current value and origin of every preference, so that they can be restored after
+/// being changed, e.g. by a test.
+pub fn snapshot() -> PrefsSnapshot {
+    PrefsSnapshot(PREFS.values().read().unwrap().clone(), PREFS.origins())
+}
+
+/// Replace every preference value and origin with the ones in `snapshot`.
+pub fn restore(snapshot: PrefsSnapshot) {
+    *PREFS.values().write().unwrap() = snapshot.0;
+    PREFS.set_origins(snapshot.1);
+}
+
+pub(crate) fn add_user_prefs(opts: &opts::Opts) {
+    if let Some(path) = user_prefs_path(opts) {
         init_user_prefs(path);
     }
 }
 
-fn user_prefs_path() -> Option<PathBuf> {
-    opts::get()
-        .config_dir
+/// The `prefs.json` that `add_user_prefs` loads for `opts`, if it exists and
+/// `--no-default-prefs` wasn't given.
+pub fn user_prefs_path(opts: &opts::Opts) -> Option<PathBuf> {
+    if opts.no_default_prefs {
+        return None;
+    }
+    opts.config_dir
         .clone()
         .or_else(|| default_config_dir())
         .map(|path| path.join("prefs.json"))
@@ -80,7 +106,7 @@ fn init_user_prefs(path: PathBuf) {
             .expect("Can't read user prefs");
         match read_prefs_map(&txt) {
             Ok(prefs) => {
-                if let Err(error) = PREFS.set_all(prefs.into_iter()) {
+                if let Err(error) = PREFS.set_all_from(prefs.into_iter(), PrefOrigin::UserFile) {
                     writeln!(&mut stderr(), "Error setting preference: {:?}", error)
                 } else {
                     Ok(())
@@ -169,6 +195,9 @@ mod gen {
                     #[serde(rename = "dom.canvas-text.enabled")]
                     enabled: bool,
                 },
+                clipboard: {
+                    enabled: bool,
+                },
                 composition_event: {
                     #[serde(rename = "dom.compositionevent.enabled")]
                     enabled: bool,
@@ -315,6 +344,9 @@ mod gen {
                 subpixel_text_antialiasing: {
                     #[serde(rename = "gfx.subpixel-text-antialiasing.enabled")]
                     enabled: bool,
+                },
+                vsync: {
+                    enabled: bool,
                 }
             },
             js: {
@@ -440,6 +472,11 @@ mod gen {
                 viewport: {
                     enabled: bool,
                 },
+                webfonts: {
+                    synchronous: {
+                        enabled: bool,
+                    }
+                },
                 writing_mode: {
                     #[serde(rename = "layout.writing-mode.enabled")]
                     enabled: bool,
@@ -451,6 +488,10 @@ mod gen {
                 }
             },
             network: {
+                http: {
+                    no_proxy: String,
+                    proxy: String,
+                },
                 http_cache: {
                     #[serde(rename = "network.http-cache.disabled")]
                     disabled: bool,
@@ -464,6 +505,9 @@ mod gen {
                 max_length: i64,
             },
             shell: {
+                hard_fail: {
+                    enabled: bool,
+                },
                 homepage: String,
                 keep_screen_on: {
                     enabled: bool,
@@ -0,0 +1,36 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Setting the environment variable would affect every other test that parses options, so this
+// lives in its own test binary.
+
+use servo_config::opts::{self, DEBUG_OPTIONS_ENV_VAR};
+use std::env;
+
+fn parse(args: &[&str]) -> Result<opts::Opts, String> {
+    let mut full_args = vec![String::from("servo")];
+    full_args.extend(args.iter().map(|arg| arg.to_string()));
+    opts::parse_opts(&full_args)
+}
+
+#[test]
+fn test_debug_options_from_env() {
+    env::set_var(DEBUG_OPTIONS_ENV_VAR, "dump-style-tree,disable-text-aa");
+
+    let opts = parse(&["-Z", "dump-flow-tree"]).unwrap();
+    assert!(opts.dump_style_tree);
+    assert!(opts.dump_flow_tree);
+    assert!(!opts.enable_text_antialiasing);
+
+    // The command line wins over the environment.
+    let opts = parse(&["-Z", "enable-text-aa"]).unwrap();
+    assert!(opts.dump_style_tree);
+    assert!(opts.enable_text_antialiasing);
+
+    env::set_var(DEBUG_OPTIONS_ENV_VAR, "not-a-debug-option");
+    let err = parse(&[]).unwrap_err();
+    assert!(err.contains(DEBUG_OPTIONS_ENV_VAR));
+
+    env::remove_var(DEBUG_OPTIONS_ENV_VAR);
+}
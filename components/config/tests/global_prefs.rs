@@ -0,0 +1,158 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// These tests change global preferences, which every test that parses options can read, so they
+// live in their own test binary. Each one takes `GLOBAL_PREFS` and restores the preferences when
+// it finishes, even if it panics.
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate servo_config;
+
+use servo_config::opts;
+use servo_config::prefs::{self, PrefOrigin, PrefValue, PrefsSnapshot};
+use std::sync::{Mutex, MutexGuard};
+
+lazy_static! {
+    static ref GLOBAL_PREFS: Mutex<()> = Mutex::new(());
+}
+
+struct PrefsGuard {
+    snapshot: Option<PrefsSnapshot>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for PrefsGuard {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            prefs::restore(snapshot);
+        }
+    }
+}
+
+fn parse(args: &[&str]) -> Result<opts::Opts, String> {
+    let mut full_args = vec![String::from("servo")];
+    full_args.extend(args.iter().map(|arg| arg.to_string()));
+    opts::parse_opts(&full_args)
+}
+
+fn lock_prefs() -> PrefsGuard {
+    // A test that panicked still restored the preferences, so a poisoned lock is fine to reuse.
+    let lock = GLOBAL_PREFS
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    PrefsGuard {
+        snapshot: Some(prefs::snapshot()),
+        _lock: lock,
+    }
+}
+
+#[test]
+fn test_hard_fail_pref() {
+    let _guard = lock_prefs();
+
+    set_pref!(shell.hard_fail.enabled, false);
+    assert!(!opts::resolve_hard_fail(false, false));
+    assert!(opts::resolve_hard_fail(true, false));
+    assert!(!opts::resolve_hard_fail(false, true));
+
+    opts::parse_pref_from_command_line("shell.hard_fail.enabled=true").unwrap();
+    assert!(opts::resolve_hard_fail(false, false));
+    assert!(opts::resolve_hard_fail(true, false));
+    assert!(!opts::resolve_hard_fail(false, true));
+    assert!(!opts::resolve_hard_fail(true, true));
+}
+
+#[test]
+fn test_headless_prefs() {
+    let _guard = lock_prefs();
+    let headless = parse(&["-z"]).unwrap();
+
+    opts::apply_headless_prefs().unwrap();
+    for &(name, value) in opts::HEADLESS_PREFS {
+        assert_eq!(prefs::pref_map().get(name), PrefValue::Bool(value));
+    }
+    assert_eq!(
+        opts::headless_prefs_applied(&headless),
+        vec![
+            "shell.native-titlebar.enabled",
+            "js.offthread_compilation.enabled",
+            "js.parallel_parsing.enabled",
+            "gfx.vsync.enabled",
+            "layout.webfonts.synchronous.enabled",
+        ]
+    );
+    assert!(opts::headless_prefs_applied(&parse(&[]).unwrap()).is_empty());
+
+    let mut updated = headless.clone();
+    updated.update_from_prefs();
+    assert!(updated.no_native_titlebar);
+    assert!(!updated.enable_vsync);
+    assert!(updated.load_webfonts_synchronously);
+
+    opts::parse_pref_from_command_line("js.parallel_parsing.enabled=true").unwrap();
+    assert_eq!(pref!(js.parallel_parsing.enabled), true);
+    assert!(!opts::headless_prefs_applied(&headless).contains(&"js.parallel_parsing.enabled"));
+
+    let dump = opts::dump_prefs(&headless);
+    assert!(dump.contains("js.offthread_compilation.enabled = false # implied by --headless\n"));
+    assert!(dump.contains("js.parallel_parsing.enabled = true\n"));
+}
+
+#[test]
+fn test_headless_prefs_keep_user_prefs() {
+    let _guard = lock_prefs();
+
+    prefs::pref_map()
+        .set_from("gfx.vsync.enabled", true, PrefOrigin::UserFile)
+        .unwrap();
+    opts::apply_headless_prefs().unwrap();
+    assert_eq!(pref!(gfx.vsync.enabled), true);
+    assert_eq!(pref!(layout.webfonts.synchronous.enabled), true);
+}
+
+#[test]
+fn test_proxy_prefs() {
+    let _guard = lock_prefs();
+
+    let opts = parse(&[
+        "--proxy",
+        "http://proxy.example.com:3128",
+        "--no-proxy",
+        "localhost, .example.org",
+    ])
+    .unwrap();
+    opts.apply_to_prefs();
+    assert_eq!(pref!(network.http.proxy), "http://proxy.example.com:3128/");
+    assert_eq!(pref!(network.http.no_proxy), "localhost,.example.org");
+}
+
+#[test]
+fn test_disable_clipboard_pref() {
+    let _guard = lock_prefs();
+
+    parse(&[]).unwrap().apply_to_prefs();
+    assert_eq!(pref!(dom.clipboard.enabled), true);
+
+    parse(&["--disable-clipboard"]).unwrap().apply_to_prefs();
+    assert_eq!(pref!(dom.clipboard.enabled), false);
+}
+
+#[test]
+fn test_command_line_pref_origin() {
+    let _guard = lock_prefs();
+
+    let key = "dom.testbinding.preference_value.truthy";
+    prefs::pref_map()
+        .set_from(key, true, PrefOrigin::UserFile)
+        .unwrap();
+    assert_eq!(prefs::origin_of(key), Some(PrefOrigin::UserFile));
+
+    opts::parse_pref_from_command_line(&format!("{}=false", key)).unwrap();
+    assert_eq!(prefs::origin_of(key), Some(PrefOrigin::CommandLine));
+
+    prefs::pref_map().reset(key).unwrap();
+    assert_eq!(prefs::origin_of(key), Some(PrefOrigin::Default));
+}
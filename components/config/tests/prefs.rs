@@ -7,7 +7,7 @@ extern crate serde_derive;
 
 use servo_config::basedir;
 use servo_config::pref_util::Preferences;
-use servo_config::prefs::{read_prefs_map, PrefValue};
+use servo_config::prefs::{self, read_prefs_map, PrefOrigin, PrefValue};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::{self, File};
@@ -92,6 +92,79 @@ fn test_set_pref() {
     assert_eq!(1, prefs.values().read().unwrap().group.nested.nested_i64);
 }
 
+#[test]
+fn test_pref_origins() -> Result<(), Box<dyn Error>> {
+    let prefs = Preferences::new(gen::TestPrefs::default(), &gen::TEST_PREF_ACCESSORS);
+    assert_eq!(Some(PrefOrigin::Default), prefs.origin("pref_i64"));
+    assert_eq!(None, prefs.origin("doesntexist"));
+
+    let user_file = vec![
+        (String::from("pref_i64"), PrefValue::from(1)),
+        (String::from("pref_bool"), PrefValue::from(true)),
+    ];
+    prefs.set_all_from(user_file, PrefOrigin::UserFile)?;
+    prefs.set_from("pref_i64", 2, PrefOrigin::CommandLine)?;
+    assert_eq!(Some(PrefOrigin::CommandLine), prefs.origin("pref_i64"));
+    assert_eq!(Some(PrefOrigin::UserFile), prefs.origin("pref_bool"));
+
+    prefs.reset("pref_i64")?;
+    assert_eq!(Some(PrefOrigin::Default), prefs.origin("pref_i64"));
+    prefs.reset_all();
+    assert_eq!(Some(PrefOrigin::Default), prefs.origin("pref_bool"));
+    Ok(())
+}
+
+#[test]
+fn test_get_all_matching() -> Result<(), Box<dyn Error>> {
+    let def_prefs: gen::TestPrefs = serde_json::from_str(DEF_JSON_STR)?;
+    let prefs = Preferences::new(def_prefs, &gen::TEST_PREF_ACCESSORS);
+    prefs.set("group.nested.nested_i64", 7)?;
+    prefs.set("pref_string", "matched")?;
+
+    assert_eq!(
+        prefs.get_all_matching("group."),
+        vec![(String::from("group.nested.nested_i64"), PrefValue::from(7)),]
+    );
+
+    let keys: Vec<String> = prefs
+        .get_all_matching("pref_")
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(keys, ["pref_bool", "pref_f64", "pref_i64", "pref_string"]);
+    assert_eq!(
+        prefs.get_all_matching("pref_string"),
+        vec![(String::from("pref_string"), PrefValue::from("matched"))]
+    );
+
+    assert!(prefs.get_all_matching("no_such_prefix").is_empty());
+    assert_eq!(
+        prefs.get_all_matching("").len(),
+        gen::TEST_PREF_ACCESSORS.len()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_typed_getters() -> Result<(), Box<dyn Error>> {
+    let def_prefs: gen::TestPrefs = serde_json::from_str(DEF_JSON_STR)?;
+    let prefs = Preferences::new(def_prefs, &gen::TEST_PREF_ACCESSORS);
+
+    assert_eq!(Some(true), prefs.get_bool("pref_bool"));
+    assert_eq!(Some(23), prefs.get_int("pref_i64"));
+    assert_eq!(Some(1.5), prefs.get_float("pref_f64"));
+    assert_eq!(Some(String::from("hello")), prefs.get_string("pref_string"));
+
+    assert_eq!(None, prefs.get_bool("pref_i64"));
+    assert_eq!(None, prefs.get_int("pref_f64"));
+    assert_eq!(None, prefs.get_float("pref_string"));
+    assert_eq!(None, prefs.get_string("pref_bool"));
+
+    assert_eq!(None, prefs.get_bool("unknown_pref"));
+    assert_eq!(None, prefs.get_string("unknown_pref"));
+    Ok(())
+}
+
 #[test]
 fn test_set_unknown_pref_is_err() -> Result<(), Box<dyn Error>> {
     let prefs = Preferences::new(gen::TestPrefs::default(), &gen::TEST_PREF_ACCESSORS);
@@ -244,6 +317,24 @@ fn test_set_all_error_on_unknown_field() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_snapshot_and_restore() -> Result<(), Box<dyn Error>> {
+    let key = "dom.testbinding.preference_value.space_string_test";
+    let before = prefs::pref_map().get(key);
+
+    let origin = prefs::origin_of(key);
+
+    let snapshot = prefs::snapshot();
+    prefs::pref_map().set_from(key, "changed", PrefOrigin::CommandLine)?;
+    assert_eq!(PrefValue::from("changed"), prefs::pref_map().get(key));
+    assert_eq!(Some(PrefOrigin::CommandLine), prefs::origin_of(key));
+
+    prefs::restore(snapshot);
+    assert_eq!(before, prefs::pref_map().get(key));
+    assert_eq!(origin, prefs::origin_of(key));
+    Ok(())
+}
+
 #[cfg(not(any(target_os = "android", feature = "uwp")))]
 #[test]
 fn test_default_config_dir_create_read_write() {
@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// These tests replace the global options, so they live in their own test binary rather than
+// alongside the tests in opts.rs that read them, and take `GLOBAL_OPTIONS` so that they don't
+// race with each other.
+
+#[macro_use]
+extern crate lazy_static;
+
+use servo_config::opts::{self, default_opts};
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref GLOBAL_OPTIONS: Mutex<()> = Mutex::new(());
+}
+
+#[test]
+fn test_set_options_returns_previous() {
+    let _guard = GLOBAL_OPTIONS.lock().unwrap();
+    let original = (*opts::get()).clone();
+
+    let mut a = default_opts();
+    a.headless = true;
+    let mut b = default_opts();
+    b.multiprocess = true;
+
+    assert_eq!(opts::set_options(a.clone()), original);
+    assert_eq!(opts::set_options(b.clone()), a);
+    assert!(opts::multiprocess());
+    assert_eq!(opts::set_options(original), b);
+    assert!(!opts::multiprocess());
+}
+
+#[test]
+fn test_get_cloned_releases_lock() {
+    let _guard = GLOBAL_OPTIONS.lock().unwrap();
+    let cloned = opts::get_cloned();
+    assert_eq!(&cloned, &*opts::get());
+
+    // `set_options` takes the write lock, which would block forever if `get_cloned` still
+    // held the read lock.
+    assert_eq!(opts::set_options(cloned.clone()), cloned);
+}
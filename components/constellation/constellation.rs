@@ -405,6 +405,16 @@ pub struct Constellation<Message, LTF, STF> {
     /// If True, exits on thread failure instead of displaying about:failure
     hard_fail: bool,
 
+    /// The page that replaces a failed pipeline when `hard_fail` is false.
+    failure_url: ServoUrl,
+
+    /// The number of top-level navigations to finish loading before shutting down, set with
+    /// --exit-after-navigation.
+    exit_after_navigations: Option<u32>,
+
+    /// The number of top-level navigations that have been committed and finished loading.
+    completed_navigations: u32,
+
     /// If set with --disable-canvas-aa, disable antialiasing on the HTML
     /// canvas element.
     /// Like --disable-text-aa, this is useful for reftests where pixel perfect
@@ -636,6 +646,8 @@ where
         random_pipeline_closure_seed: Option<usize>,
         is_running_problem_test: bool,
         hard_fail: bool,
+        failure_url: ServoUrl,
+        exit_after_navigations: Option<u32>,
         enable_canvas_antialiasing: bool,
     ) -> (Sender<FromCompositorMsg>, IpcSender<SWManagerMsg>) {
         let (compositor_sender, compositor_receiver) = unbounded();
@@ -728,12 +740,16 @@ where
                         device_pixel_ratio: TypedScale::new(device_pixels_per_px.unwrap_or(1.0)),
                     },
                     phantom: PhantomData,
-                    clipboard_ctx: match ClipboardContext::new() {
-                        Ok(c) => Some(c),
-                        Err(e) => {
-                            warn!("Error creating clipboard context ({})", e);
-                            None
-                        },
+                    clipboard_ctx: if pref!(dom.clipboard.enabled) {
+                        match ClipboardContext::new() {
+                            Ok(c) => Some(c),
+                            Err(e) => {
+                                warn!("Error creating clipboard context ({})", e);
+                                None
+                            },
+                        }
+                    } else {
+                        None
                     },
                     webdriver: WebDriverData::new(),
                     scheduler_chan: TimerScheduler::start(),
@@ -756,6 +772,9 @@ where
                     pressed_mouse_buttons: 0,
                     is_running_problem_test,
                     hard_fail,
+                    failure_url,
+                    exit_after_navigations,
+                    completed_navigations: 0,
                     enable_canvas_antialiasing,
                 };
 
@@ -1907,13 +1926,13 @@ where
             ExitPipelineMode::Force,
         );
 
-        let failure_url = ServoUrl::parse("about:failure").expect("infallible");
+        let failure_url = self.failure_url.clone();
 
         if pipeline_url == failure_url {
-            return error!("about:failure failed");
+            return error!("{} failed", failure_url);
         }
 
-        warn!("creating replacement pipeline for about:failure");
+        warn!("creating replacement pipeline for {}", failure_url);
 
         let new_pipeline_id = PipelineId::new();
         let load_data = LoadData::new(failure_url, None, None, None);
@@ -2720,12 +2739,27 @@ where
                     Some(top_level_browsing_context_id),
                     EmbedderMsg::LoadComplete,
                 ));
+                self.handle_navigation_completed();
             }
         } else {
             self.handle_subframe_loaded(pipeline_id);
         }
     }
 
+    /// Count a top-level navigation that has been committed and finished loading, and shut down
+    /// once --exit-after-navigation of them have.
+    fn handle_navigation_completed(&mut self) {
+        let limit = match self.exit_after_navigations {
+            Some(limit) => limit,
+            None => return,
+        };
+        self.completed_navigations += 1;
+        if self.completed_navigations >= limit {
+            debug!("Exiting after {} navigations.", self.completed_navigations);
+            self.handle_exit();
+        }
+    }
+
     fn handle_navigated_to_fragment(
         &mut self,
         pipeline_id: PipelineId,
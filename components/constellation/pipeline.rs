@@ -293,7 +293,7 @@ impl Pipeline {
                     script_chan: script_chan.clone(),
                     load_data: state.load_data.clone(),
                     script_port: script_port,
-                    opts: (*opts::get()).clone(),
+                    opts: opts::serialize_for_ipc(),
                     prefs: prefs::pref_map().iter().collect(),
                     pipeline_port: pipeline_port,
                     pipeline_namespace_id: state.pipeline_namespace_id,
@@ -498,7 +498,8 @@ pub struct UnprivilegedPipelineContent {
     script_chan: IpcSender<ConstellationControlMsg>,
     load_data: LoadData,
     script_port: IpcReceiver<ConstellationControlMsg>,
-    opts: Opts,
+    /// The options, as written by `opts::serialize_for_ipc`.
+    opts: Vec<u8>,
     prefs: HashMap<String, PrefValue>,
     pipeline_port: IpcReceiver<LayoutControlMsg>,
     pipeline_namespace_id: PipelineNamespaceId,
@@ -521,6 +522,7 @@ impl UnprivilegedPipelineContent {
         LTF: LayoutThreadFactory<Message = Message>,
         STF: ScriptThreadFactory<Message = Message>,
     {
+        let opts = self.opts().expect("Failed to read the pipeline options");
         let image_cache = Arc::new(ImageCacheImpl::new(self.webrender_api_sender.create_api()));
         let paint_time_metrics = PaintTimeMetrics::new(
             self.id,
@@ -559,17 +561,15 @@ impl UnprivilegedPipelineContent {
                 layout_is_busy: layout_thread_busy_flag.clone(),
             },
             self.load_data.clone(),
-            self.opts.profile_script_events,
-            self.opts.print_pwm,
-            self.opts.relayout_event,
-            self.opts.output_file.is_some() ||
-                self.opts.exit_after_load ||
-                self.opts.webdriver_port.is_some(),
-            self.opts.unminify_js,
-            self.opts.userscripts,
-            self.opts.headless,
-            self.opts.replace_surrogates,
-            self.opts.user_agent,
+            opts.profile_script_events,
+            opts.print_pwm,
+            opts.relayout_event,
+            opts.output_file.is_some() || opts.exit_after_load || opts.webdriver_port.is_some(),
+            opts.unminify_js,
+            opts.userscripts,
+            opts.headless,
+            opts.replace_surrogates,
+            opts.user_agent,
         );
 
         LTF::create(
@@ -591,17 +591,17 @@ impl UnprivilegedPipelineContent {
             self.webrender_document,
             paint_time_metrics,
             layout_thread_busy_flag.clone(),
-            self.opts.load_webfonts_synchronously,
-            self.opts.initial_window_size,
-            self.opts.device_pixels_per_px,
-            self.opts.dump_display_list,
-            self.opts.dump_display_list_json,
-            self.opts.dump_style_tree,
-            self.opts.dump_rule_tree,
-            self.opts.relayout_event,
-            self.opts.nonincremental_layout,
-            self.opts.trace_layout,
-            self.opts.dump_flow_tree,
+            opts.load_webfonts_synchronously,
+            opts.initial_window_size.size,
+            opts.device_pixels_per_px,
+            opts.dump_display_list,
+            opts.dump_display_list_json,
+            opts.dump_style_tree,
+            opts.dump_rule_tree,
+            opts.relayout_event,
+            opts.nonincremental_layout,
+            opts.trace_layout,
+            opts.dump_flow_tree,
         );
 
         if wait_for_completion {
@@ -672,7 +672,7 @@ impl UnprivilegedPipelineContent {
             .expect("Failed to create IPC one-shot server.");
 
         // If there is a sandbox, use the `gaol` API to create the child process.
-        if self.opts.sandbox {
+        if opts::get().sandbox {
             let mut command = sandbox::Command::me().expect("Failed to get current sandbox.");
             self.setup_common(&mut command, token);
 
@@ -730,8 +730,8 @@ impl UnprivilegedPipelineContent {
         &self.script_to_constellation_chan
     }
 
-    pub fn opts(&self) -> Opts {
-        self.opts.clone()
+    pub fn opts(&self) -> Result<Opts, String> {
+        opts::deserialize_from_ipc(&self.opts)
     }
 
     pub fn prefs(&self) -> HashMap<String, PrefValue> {
@@ -3,42 +3,331 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
 use crate::hosts::replace_host;
-use hyper::client::connect::{Connect, Destination};
+use futures::future::{self, loop_fn, Loop};
+use futures::Poll;
+use hyper::client::connect::{Connect, Connected, Destination};
 use hyper::client::HttpConnector as HyperHttpConnector;
 use hyper::rt::Future;
 use hyper::{Body, Client};
 use hyper_openssl::HttpsConnector;
 use openssl::ssl::{SslConnector, SslConnectorBuilder, SslMethod, SslOptions};
 use openssl::x509;
+use servo_url::ServoUrl;
+use std::io::{self, Read, Write};
+use std::net::IpAddr;
+use tokio::io::{read_exact, write_all, AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
 use tokio::prelude::future::Executor;
+use tokio_openssl::{SslConnectorExt, SslStream};
 
 pub const BUF_SIZE: usize = 32768;
 
+/// The longest response to a CONNECT request that a proxy may send before its blank line.
+const MAX_PROXY_RESPONSE_HEAD: usize = 8192;
+
+type IoFuture<T> = Box<dyn Future<Item = T, Error = io::Error> + Send>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ProxyKind {
+    Http,
+    Https,
+    Socks5,
+}
+
+/// A proxy that HTTP connections go through, and the hosts that are contacted directly
+/// instead. WebSocket connections don't use it.
+#[derive(Clone)]
+pub struct ProxyConfig {
+    kind: ProxyKind,
+    host: String,
+    port: u16,
+    no_proxy: Vec<String>,
+    tls: SslConnector,
+}
+
+impl ProxyConfig {
+    /// `url` is an http, https or socks5 URL, as accepted by `--proxy`. `no_proxy` holds host
+    /// names, which also match their subdomains; a leading `.` is ignored, and `*` matches every
+    /// host. `tls` is used to connect to an https proxy.
+    pub fn new(url: &ServoUrl, no_proxy: &[String], tls: SslConnector) -> ProxyConfig {
+        let kind = match url.scheme() {
+            "https" => ProxyKind::Https,
+            "socks5" => ProxyKind::Socks5,
+            _ => ProxyKind::Http,
+        };
+        ProxyConfig {
+            kind: kind,
+            host: url.host_str().unwrap_or("").to_owned(),
+            // The URL parser doesn't know the default port of socks5.
+            port: url.port_or_known_default().unwrap_or(1080),
+            no_proxy: no_proxy
+                .iter()
+                .map(|host| host.trim_start_matches('.').to_ascii_lowercase())
+                .collect(),
+            tls: tls,
+        }
+    }
+
+    /// Whether a connection to `host` goes through the proxy.
+    fn is_used_for(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        !self
+            .no_proxy
+            .iter()
+            .any(|entry| entry == "*" || host == *entry || host.ends_with(&format!(".{}", entry)))
+    }
+}
+
+/// A connection made by `HttpConnector`, which is only encrypted when it goes to an https proxy.
+#[derive(Debug)]
+pub enum ProxyStream {
+    Tcp(TcpStream),
+    Tls(SslStream<TcpStream>),
+}
+
+impl Read for ProxyStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            ProxyStream::Tcp(ref mut stream) => stream.read(buf),
+            ProxyStream::Tls(ref mut stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for ProxyStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            ProxyStream::Tcp(ref mut stream) => stream.write(buf),
+            ProxyStream::Tls(ref mut stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            ProxyStream::Tcp(ref mut stream) => stream.flush(),
+            ProxyStream::Tls(ref mut stream) => stream.flush(),
+        }
+    }
+}
+
+impl AsyncRead for ProxyStream {}
+
+impl AsyncWrite for ProxyStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        match *self {
+            ProxyStream::Tcp(ref mut stream) => AsyncWrite::shutdown(stream),
+            ProxyStream::Tls(ref mut stream) => AsyncWrite::shutdown(stream),
+        }
+    }
+}
+
 pub struct HttpConnector {
     inner: HyperHttpConnector,
+    proxy: Option<ProxyConfig>,
 }
 
 impl HttpConnector {
-    fn new() -> HttpConnector {
+    fn new(proxy: Option<ProxyConfig>) -> HttpConnector {
         let mut inner = HyperHttpConnector::new(4);
         inner.enforce_http(false);
         inner.set_happy_eyeballs_timeout(None);
-        HttpConnector { inner }
+        HttpConnector { inner, proxy }
     }
 }
 
 impl Connect for HttpConnector {
-    type Transport = <HyperHttpConnector as Connect>::Transport;
-    type Error = <HyperHttpConnector as Connect>::Error;
-    type Future = <HyperHttpConnector as Connect>::Future;
+    type Transport = ProxyStream;
+    type Error = io::Error;
+    type Future = IoFuture<(ProxyStream, Connected)>;
 
     fn connect(&self, dest: Destination) -> Self::Future {
-        // Perform host replacement when making the actual TCP connection.
-        let mut new_dest = dest.clone();
-        let addr = replace_host(dest.host());
-        new_dest.set_host(&*addr).unwrap();
-        self.inner.connect(new_dest)
+        let proxy = match self.proxy {
+            Some(ref proxy) if proxy.is_used_for(dest.host()) => proxy,
+            _ => {
+                // Perform host replacement when making the actual TCP connection.
+                let mut new_dest = dest.clone();
+                let addr = replace_host(dest.host());
+                new_dest.set_host(&*addr).unwrap();
+                return Box::new(
+                    self.inner
+                        .connect(new_dest)
+                        .map(|(stream, connected)| (ProxyStream::Tcp(stream), connected)),
+                );
+            },
+        };
+
+        let mut proxy_dest = dest.clone();
+        proxy_dest.set_host(&*replace_host(&proxy.host)).unwrap();
+        proxy_dest.set_port(Some(proxy.port));
+        let stream = self.inner.connect(proxy_dest).map(|(stream, _)| stream);
+
+        // An https request is tunnelled through the proxy, so that `HttpsConnector` can set up
+        // TLS with the server itself. An http request is sent to the proxy with the absolute URL
+        // as its target, which hyper does for connections marked as proxied.
+        let host = dest.host().to_owned();
+        let port = dest
+            .port()
+            .unwrap_or_else(|| if dest.scheme() == "https" { 443 } else { 80 });
+        let tunnelled = dest.scheme() == "https";
+        match proxy.kind {
+            ProxyKind::Socks5 => Box::new(
+                stream
+                    .and_then(move |stream| socks5_connect(stream, host, port))
+                    .map(|stream| (ProxyStream::Tcp(stream), Connected::new())),
+            ),
+            ProxyKind::Http if tunnelled => Box::new(
+                stream
+                    .and_then(move |stream| tunnel(stream, host, port))
+                    .map(|stream| (ProxyStream::Tcp(stream), Connected::new())),
+            ),
+            ProxyKind::Http => Box::new(
+                stream.map(|stream| (ProxyStream::Tcp(stream), Connected::new().proxy(true))),
+            ),
+            ProxyKind::Https => {
+                let tls = proxy.tls.clone();
+                let proxy_host = proxy.host.clone();
+                let stream = stream.and_then(move |stream| {
+                    tls.connect_async(&proxy_host, stream)
+                        .map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string()))
+                });
+                if tunnelled {
+                    Box::new(
+                        stream
+                            .and_then(move |stream| tunnel(stream, host, port))
+                            .map(|stream| (ProxyStream::Tls(stream), Connected::new())),
+                    )
+                } else {
+                    Box::new(
+                        stream
+                            .map(|stream| (ProxyStream::Tls(stream), Connected::new().proxy(true))),
+                    )
+                }
+            },
+        }
+    }
+}
+
+/// Ask an http(s) proxy to open a tunnel to `host:port`.
+fn tunnel<S>(stream: S, host: String, port: u16) -> IoFuture<S>
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let request = format!(
+        "CONNECT {0}:{1} HTTP/1.1\r\nHost: {0}:{1}\r\n\r\n",
+        host, port
+    );
+    Box::new(
+        write_all(stream, request.into_bytes())
+            .and_then(|(stream, _)| read_response_head(stream))
+            .and_then(|(stream, head)| {
+                let head = String::from_utf8_lossy(&head);
+                let status_line = head.lines().next().unwrap_or("");
+                match status_line.split(' ').nth(1) {
+                    Some(status) if status.starts_with('2') => Ok(stream),
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("proxy refused to open a tunnel ({})", status_line),
+                    )),
+                }
+            }),
+    )
+}
+
+/// Read the status line and headers of a proxy's response. This reads a byte at a time, so that
+/// nothing that follows them on the tunnelled connection is consumed.
+fn read_response_head<S>(stream: S) -> IoFuture<(S, Vec<u8>)>
+where
+    S: AsyncRead + Send + 'static,
+{
+    Box::new(loop_fn((stream, vec![]), |(stream, mut head)| {
+        read_exact(stream, [0u8; 1]).and_then(move |(stream, byte)| {
+            head.push(byte[0]);
+            if head.ends_with(b"\r\n\r\n") {
+                Ok(Loop::Break((stream, head)))
+            } else if head.len() >= MAX_PROXY_RESPONSE_HEAD {
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "proxy response headers are too long",
+                ))
+            } else {
+                Ok(Loop::Continue((stream, head)))
+            }
+        })
+    }))
+}
+
+/// Ask a socks5 proxy, which mustn't require authentication, to connect to `host:port`.
+/// See RFC 1928.
+fn socks5_connect<S>(stream: S, host: String, port: u16) -> IoFuture<S>
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let mut request = vec![5u8, 1, 0];
+    match host
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+    {
+        Ok(IpAddr::V4(address)) => {
+            request.push(1);
+            request.extend_from_slice(&address.octets());
+        },
+        Ok(IpAddr::V6(address)) => {
+            request.push(4);
+            request.extend_from_slice(&address.octets());
+        },
+        Err(_) if host.len() <= 255 => {
+            request.push(3);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        },
+        Err(_) => {
+            return Box::new(future::err(io::Error::new(
+                io::ErrorKind::Other,
+                "host name is too long for a socks5 proxy",
+            )));
+        },
     }
+    request.push((port >> 8) as u8);
+    request.push(port as u8);
+
+    Box::new(
+        // Offer only the "no authentication required" method.
+        write_all(stream, [5u8, 1, 0])
+            .and_then(|(stream, _)| read_exact(stream, [0u8; 2]))
+            .and_then(|(stream, reply)| {
+                if reply == [5, 0] {
+                    Ok(stream)
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "socks5 proxy requires authentication",
+                    ))
+                }
+            })
+            .and_then(move |stream| write_all(stream, request))
+            .and_then(|(stream, _)| read_exact(stream, [0u8; 4]))
+            .and_then(|(stream, reply)| -> IoFuture<(S, Vec<u8>)> {
+                if reply[0] != 5 || reply[1] != 0 {
+                    return Box::new(future::err(io::Error::new(
+                        io::ErrorKind::Other,
+                        format!("socks5 proxy refused to connect (error {})", reply[1]),
+                    )));
+                }
+                // Skip the address and port that the proxy bound.
+                match reply[3] {
+                    1 => Box::new(read_exact(stream, vec![0u8; 4 + 2])),
+                    4 => Box::new(read_exact(stream, vec![0u8; 16 + 2])),
+                    3 => Box::new(read_exact(stream, [0u8; 1]).and_then(|(stream, length)| {
+                        read_exact(stream, vec![0u8; length[0] as usize + 2])
+                    })),
+                    _ => Box::new(future::err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "socks5 proxy replied with an unknown address type",
+                    ))),
+                }
+            })
+            .map(|(stream, _)| stream),
+    )
 }
 
 pub type Connector = HttpsConnector<HttpConnector>;
@@ -89,13 +378,14 @@ pub fn create_ssl_connector_builder(certs: &str) -> SslConnectorBuilder {
 
 pub fn create_http_client<E>(
     ssl_connector_builder: SslConnectorBuilder,
+    proxy: Option<ProxyConfig>,
     executor: E,
 ) -> Client<Connector, Body>
 where
     E: Executor<Box<dyn Future<Error = (), Item = ()> + Send + 'static>> + Sync + Send + 'static,
 {
     let connector =
-        HttpsConnector::with_connector(HttpConnector::new(), ssl_connector_builder).unwrap();
+        HttpsConnector::with_connector(HttpConnector::new(proxy), ssl_connector_builder).unwrap();
     Client::builder()
         .http1_title_case_headers(true)
         .executor(executor)
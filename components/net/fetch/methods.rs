@@ -24,6 +24,7 @@ use net_traits::request::{Origin, ResponseTainting, Window};
 use net_traits::response::{Response, ResponseBody, ResponseType};
 use net_traits::ResourceAttribute;
 use net_traits::{FetchTaskTarget, NetworkError, ReferrerPolicy, ResourceFetchTiming};
+use servo_config::opts;
 use servo_url::ServoUrl;
 use std::borrow::Cow;
 use std::fs::File;
@@ -174,6 +175,13 @@ pub fn main_fetch(
             "Request attempted on bad port".into(),
         )));
     }
+    if let Some(max_url_length) = opts::get().max_url_length {
+        if request.current_url().as_str().len() > max_url_length {
+            response = Some(Response::network_error(NetworkError::Internal(
+                "URL exceeds the maximum length".into(),
+            )));
+        }
+    }
     // TODO: handle blocking as mixed content.
     // TODO: handle blocking by content security policy.
 
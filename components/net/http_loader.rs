@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use crate::connector::{create_http_client, Connector};
+use crate::connector::{create_http_client, Connector, ProxyConfig};
 use crate::cookie;
 use crate::cookie_storage::CookieStorage;
 use crate::decoder::Decoder;
@@ -44,6 +44,7 @@ use net_traits::response::{HttpsState, Response, ResponseBody, ResponseType};
 use net_traits::{CookieSource, FetchMetadata, NetworkError, ReferrerPolicy};
 use net_traits::{RedirectStartValue, ResourceAttribute, ResourceFetchTiming};
 use openssl::ssl::SslConnectorBuilder;
+use servo_config::opts;
 use servo_url::{ImmutableOrigin, ServoUrl};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
@@ -71,14 +72,21 @@ pub struct HttpState {
 }
 
 impl HttpState {
-    pub fn new(ssl_connector_builder: SslConnectorBuilder) -> HttpState {
+    pub fn new(
+        ssl_connector_builder: SslConnectorBuilder,
+        proxy: Option<ProxyConfig>,
+    ) -> HttpState {
         HttpState {
             hsts_list: RwLock::new(HstsList::new()),
             cookie_jar: RwLock::new(CookieStorage::new(150)),
             auth_cache: RwLock::new(AuthCache::new()),
             history_states: RwLock::new(HashMap::new()),
             http_cache: RwLock::new(HttpCache::new()),
-            client: create_http_client(ssl_connector_builder, HANDLE.lock().unwrap().executor()),
+            client: create_http_client(
+                ssl_connector_builder,
+                proxy,
+                HANDLE.lock().unwrap().executor(),
+            ),
         }
     }
 }
@@ -142,10 +150,9 @@ pub fn set_default_accept_language(headers: &mut HeaderMap) {
     }
 
     // TODO(eijebong): Change this once typed headers are done
-    headers.insert(
-        header::ACCEPT_LANGUAGE,
-        HeaderValue::from_static("en-US, en; q=0.5"),
-    );
+    let accept_language = HeaderValue::from_str(&opts::get().effective_accept_language())
+        .unwrap_or_else(|_| HeaderValue::from_static("en-US, en; q=0.5"));
+    headers.insert(header::ACCEPT_LANGUAGE, accept_language);
 }
 
 /// <https://w3c.github.io/webappsec-referrer-policy/#referrer-policy-state-no-referrer-when-downgrade>
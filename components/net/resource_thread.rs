@@ -4,7 +4,7 @@
 
 //! A thread that takes a URL and streams back the binary data.
 
-use crate::connector::{create_http_client, create_ssl_connector_builder};
+use crate::connector::{create_http_client, create_ssl_connector_builder, ProxyConfig};
 use crate::cookie;
 use crate::cookie_storage::CookieStorage;
 use crate::fetch::cors_cache::CorsCache;
@@ -34,6 +34,7 @@ use profile_traits::mem::ProfilerChan as MemProfilerChan;
 use profile_traits::mem::{Report, ReportKind, ReportsChan};
 use profile_traits::time::ProfilerChan;
 use serde::{Deserialize, Serialize};
+use servo_config::opts::NetworkingConfig;
 use servo_url::ServoUrl;
 use std::borrow::{Cow, ToOwned};
 use std::collections::HashMap;
@@ -47,22 +48,20 @@ use std::thread;
 
 /// Returns a tuple of (public, private) senders to the new threads.
 pub fn new_resource_threads(
-    user_agent: Cow<'static, str>,
+    networking_config: NetworkingConfig,
     devtools_chan: Option<Sender<DevtoolsControlMsg>>,
     time_profiler_chan: ProfilerChan,
     mem_profiler_chan: MemProfilerChan,
     embedder_proxy: EmbedderProxy,
     config_dir: Option<PathBuf>,
-    certificate_path: Option<String>,
 ) -> (ResourceThreads, ResourceThreads) {
     let (public_core, private_core) = new_core_resource_thread(
-        user_agent,
+        networking_config,
         devtools_chan,
         time_profiler_chan,
         mem_profiler_chan,
         embedder_proxy,
         config_dir.clone(),
-        certificate_path,
     );
     let storage: IpcSender<StorageThreadMsg> = StorageThreadFactory::new(config_dir);
     (
@@ -73,13 +72,12 @@ pub fn new_resource_threads(
 
 /// Create a CoreResourceThread
 pub fn new_core_resource_thread(
-    user_agent: Cow<'static, str>,
+    networking_config: NetworkingConfig,
     devtools_chan: Option<Sender<DevtoolsControlMsg>>,
     time_profiler_chan: ProfilerChan,
     mem_profiler_chan: MemProfilerChan,
     embedder_proxy: EmbedderProxy,
     config_dir: Option<PathBuf>,
-    certificate_path: Option<String>,
 ) -> (CoreResourceThread, CoreResourceThread) {
     let (public_setup_chan, public_setup_port) = ipc::channel().unwrap();
     let (private_setup_chan, private_setup_port) = ipc::channel().unwrap();
@@ -89,17 +87,17 @@ pub fn new_core_resource_thread(
         .name("ResourceManager".to_owned())
         .spawn(move || {
             let resource_manager = CoreResourceManager::new(
-                user_agent,
+                networking_config.user_agent.clone(),
                 devtools_chan,
                 time_profiler_chan,
                 embedder_proxy,
-                certificate_path.clone(),
+                networking_config.certificate_path.clone(),
             );
 
             let mut channel_manager = ResourceChannelManager {
                 resource_manager,
                 config_dir,
-                certificate_path,
+                networking_config,
             };
 
             mem_profiler_chan.run_with_memory_reporting(
@@ -116,12 +114,12 @@ pub fn new_core_resource_thread(
 struct ResourceChannelManager {
     resource_manager: CoreResourceManager,
     config_dir: Option<PathBuf>,
-    certificate_path: Option<String>,
+    networking_config: NetworkingConfig,
 }
 
 fn create_http_states(
     config_dir: Option<&Path>,
-    certificate_path: Option<String>,
+    networking_config: &NetworkingConfig,
 ) -> (Arc<HttpState>, Arc<HttpState>) {
     let mut hsts_list = HstsList::from_servo_preload();
     let mut auth_cache = AuthCache::new();
@@ -133,11 +131,19 @@ fn create_http_states(
         read_json_from_file(&mut cookie_jar, config_dir, "cookie_jar.json");
     }
 
-    let certs = match certificate_path {
+    let certs = match networking_config.certificate_path {
         Some(ref path) => fs::read_to_string(path).expect("Couldn't not find certificate file"),
         None => resources::read_string(Resource::SSLCertificates),
     };
 
+    let proxy = networking_config.proxy.as_ref().map(|url| {
+        ProxyConfig::new(
+            url,
+            &networking_config.no_proxy,
+            create_ssl_connector_builder(&certs).build(),
+        )
+    });
+
     let ssl_connector_builder = create_ssl_connector_builder(&certs);
     let http_state = HttpState {
         cookie_jar: RwLock::new(cookie_jar),
@@ -145,11 +151,15 @@ fn create_http_states(
         http_cache: RwLock::new(http_cache),
         hsts_list: RwLock::new(hsts_list),
         history_states: RwLock::new(HashMap::new()),
-        client: create_http_client(ssl_connector_builder, HANDLE.lock().unwrap().executor()),
+        client: create_http_client(
+            ssl_connector_builder,
+            proxy.clone(),
+            HANDLE.lock().unwrap().executor(),
+        ),
     };
 
     let private_ssl_client = create_ssl_connector_builder(&certs);
-    let private_http_state = HttpState::new(private_ssl_client);
+    let private_http_state = HttpState::new(private_ssl_client, proxy);
 
     (Arc::new(http_state), Arc::new(private_http_state))
 }
@@ -164,7 +174,7 @@ impl ResourceChannelManager {
     ) {
         let (public_http_state, private_http_state) = create_http_states(
             self.config_dir.as_ref().map(Deref::deref),
-            self.certificate_path.clone(),
+            &self.networking_config,
         );
 
         let mut rx_set = IpcReceiverSet::new().unwrap();
@@ -372,16 +382,28 @@ where
         Ok(d) => json_encoded = d,
         Err(_) => return,
     }
+    // The config dir defaults to a platform directory that may not exist yet.
+    if let Err(why) = fs::create_dir_all(config_dir) {
+        warn!(
+            "couldn't create {}: {}",
+            config_dir.display(),
+            Error::description(&why)
+        );
+        return;
+    }
     let path = config_dir.join(filename);
     let display = path.display();
 
     let mut file = match File::create(&path) {
-        Err(why) => panic!("couldn't create {}: {}", display, Error::description(&why)),
+        Err(why) => {
+            warn!("couldn't create {}: {}", display, Error::description(&why));
+            return;
+        },
         Ok(file) => file,
     };
 
     match file.write_all(json_encoded.as_bytes()) {
-        Err(why) => panic!(
+        Err(why) => warn!(
             "couldn't write to {}: {}",
             display,
             Error::description(&why)
@@ -24,7 +24,7 @@ use hyper::body::Body;
 use hyper::{Request as HyperRequest, Response as HyperResponse};
 use mime::{self, Mime};
 use msg::constellation_msg::TEST_PIPELINE_ID;
-use net::connector::create_ssl_connector_builder;
+use net::connector::{create_ssl_connector_builder, ProxyConfig};
 use net::fetch::cors_cache::CorsCache;
 use net::fetch::methods::{self, CancellationListener, FetchContext};
 use net::filemanager_thread::FileManager;
@@ -36,6 +36,7 @@ use net_traits::{
     FetchTaskTarget, IncludeSubdomains, NetworkError, ReferrerPolicy, ResourceFetchTiming,
     ResourceTimingType,
 };
+use servo_config::opts;
 use servo_url::{ImmutableOrigin, ServoUrl};
 use std::fs::File;
 use std::io::Read;
@@ -660,7 +661,7 @@ fn test_fetch_with_hsts() {
     let ssl_client = create_ssl_connector_builder(&ca_content);
 
     let mut context = FetchContext {
-        state: Arc::new(HttpState::new(ssl_client)),
+        state: Arc::new(HttpState::new(ssl_client, None)),
         user_agent: DEFAULT_USER_AGENT.into(),
         devtools_chan: None,
         filemanager: FileManager::new(create_embedder_proxy()),
@@ -689,6 +690,50 @@ fn test_fetch_with_hsts() {
     );
 }
 
+#[test]
+fn test_fetch_through_http_proxy() {
+    // The proxy answers with the target of each request it receives.
+    let handler = move |request: HyperRequest<Body>, response: &mut HyperResponse<Body>| {
+        *response.body_mut() = request.uri().to_string().into_bytes().into();
+    };
+    let (server, proxy_url) = make_server(handler);
+
+    let fetch_through_proxy = |url: ServoUrl, no_proxy: &[String]| {
+        let proxy = ProxyConfig::new(
+            &proxy_url,
+            no_proxy,
+            create_ssl_connector_builder("").build(),
+        );
+        let mut context = new_fetch_context(None, None);
+        context.state = Arc::new(HttpState::new(
+            create_ssl_connector_builder(""),
+            Some(proxy),
+        ));
+        let origin = Origin::Origin(url.origin());
+        let mut request = Request::new(url, Some(origin), None);
+        request.referrer = Referrer::NoReferrer;
+        let response = fetch_with_context(&mut request, &mut context);
+        let body = response.body.lock().unwrap();
+        body.clone()
+    };
+
+    let proxied = fetch_through_proxy(
+        ServoUrl::parse("http://servo.invalid/proxied").unwrap(),
+        &[],
+    );
+    let direct = fetch_through_proxy(
+        proxy_url.join("direct").unwrap(),
+        &[String::from("localhost")],
+    );
+    let _ = server.close();
+
+    assert_eq!(
+        proxied,
+        ResponseBody::Done(b"http://servo.invalid/proxied".to_vec())
+    );
+    assert_eq!(direct, ResponseBody::Done(b"/direct".to_vec()));
+}
+
 #[test]
 fn test_fetch_with_sri_network_error() {
     static MESSAGE: &'static [u8] = b"alert('Hello, Network Error');";
@@ -1083,7 +1128,7 @@ fn test_fetch_with_devtools() {
 
     headers.insert(
         header::ACCEPT_LANGUAGE,
-        HeaderValue::from_static("en-US, en; q=0.5"),
+        HeaderValue::from_str(&opts::get().effective_accept_language()).unwrap(),
     );
 
     headers.typed_insert::<UserAgent>(DEFAULT_USER_AGENT.parse().unwrap());
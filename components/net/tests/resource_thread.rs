@@ -9,6 +9,7 @@ use net::test::parse_hostsfile;
 use net_traits::CoreResourceMsg;
 use profile_traits::mem::ProfilerChan as MemProfilerChan;
 use profile_traits::time::ProfilerChan;
+use servo_config::opts;
 use std::net::IpAddr;
 
 fn ip(s: &str) -> IpAddr {
@@ -21,13 +22,12 @@ fn test_exit() {
     let (mtx, _mrx) = ipc::channel().unwrap();
     let (sender, receiver) = ipc::channel().unwrap();
     let (resource_thread, _private_resource_thread) = new_core_resource_thread(
-        "".into(),
+        opts::default_opts().clone_networking_config(),
         None,
         ProfilerChan(tx),
         MemProfilerChan(mtx),
         create_embedder_proxy(),
         None,
-        None,
     );
     resource_thread.send(CoreResourceMsg::Exit(sender)).unwrap();
     receiver.recv().unwrap();
@@ -9,9 +9,14 @@ use ipc_channel::ipc::{self, IpcReceiver};
 use ipc_channel::router::ROUTER;
 use profile_traits::mem::ReportsChan;
 use profile_traits::mem::{ProfilerChan, ProfilerMsg, ReportKind, Reporter, ReporterRequest};
+use servo_config::opts::MemProfilerOutput;
 use std::borrow::ToOwned;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs::{self, OpenOptions};
+use std::io::Write as IoWrite;
+use std::path::Path;
 use std::thread;
 use std::time::Instant;
 
@@ -24,14 +29,19 @@ pub struct Profiler {
 
     /// Instant at which this profiler was created.
     created: Instant,
+
+    /// The file that reports are appended to, or `None` to print them to stdout.
+    file_name: Option<String>,
 }
 
 const JEMALLOC_HEAP_ALLOCATED_STR: &'static str = "jemalloc-heap-allocated";
 const SYSTEM_HEAP_ALLOCATED_STR: &'static str = "system-heap-allocated";
 
 impl Profiler {
-    pub fn create(period: Option<f64>) -> ProfilerChan {
+    pub fn create(output: Option<MemProfilerOutput>) -> ProfilerChan {
         let (chan, port) = ipc::channel().unwrap();
+        let period = output.as_ref().map(|output| output.interval);
+        let file_name = output.and_then(|output| output.file_name);
 
         // Create the timer thread if a period was provided.
         if let Some(period) = period {
@@ -52,7 +62,7 @@ impl Profiler {
         thread::Builder::new()
             .name("Memory profiler".to_owned())
             .spawn(move || {
-                let mut mem_profiler = Profiler::new(port);
+                let mut mem_profiler = Profiler::new(port, file_name);
                 mem_profiler.start();
             })
             .expect("Thread spawning failed");
@@ -78,11 +88,12 @@ impl Profiler {
         mem_profiler_chan
     }
 
-    pub fn new(port: IpcReceiver<ProfilerMsg>) -> Profiler {
+    pub fn new(port: IpcReceiver<ProfilerMsg>, file_name: Option<String>) -> Profiler {
         Profiler {
             port: port,
             reporters: HashMap::new(),
             created: Instant::now(),
+            file_name: file_name,
         }
     }
 
@@ -127,8 +138,9 @@ impl Profiler {
 
     fn handle_print_msg(&self) {
         let elapsed = self.created.elapsed();
-        println!("Begin memory reports {}", elapsed.as_secs());
-        println!("|");
+        let mut output = String::new();
+        writeln!(output, "Begin memory reports {}", elapsed.as_secs()).unwrap();
+        writeln!(output, "|").unwrap();
 
         // Collect reports from memory reporters.
         //
@@ -207,11 +219,26 @@ impl Profiler {
             );
         }
 
-        forest.print();
-
-        println!("|");
-        println!("End memory reports");
-        println!("");
+        forest.print(&mut output);
+
+        writeln!(output, "|").unwrap();
+        writeln!(output, "End memory reports").unwrap();
+        writeln!(output, "").unwrap();
+
+        match self.file_name {
+            Some(ref file_name) => {
+                // The file may be under an --output-dir that doesn't exist yet.
+                let result = Path::new(file_name)
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|()| OpenOptions::new().create(true).append(true).open(file_name))
+                    .and_then(|mut file| file.write_all(output.as_bytes()));
+                if let Err(e) = result {
+                    warn!("Couldn't write memory reports to {}: {}", file_name, e);
+                }
+            },
+            None => print!("{}", output),
+        }
     }
 }
 
@@ -292,7 +319,7 @@ impl ReportsTree {
         self.size
     }
 
-    fn print(&self, depth: i32) {
+    fn print(&self, depth: i32, output: &mut String) {
         if !self.children.is_empty() {
             assert_eq!(self.count, 0);
         }
@@ -308,16 +335,18 @@ impl ReportsTree {
         } else {
             "".to_owned()
         };
-        println!(
+        writeln!(
+            output,
             "|{}{:8.2} MiB -- {}{}",
             indent_str,
             (self.size as f64) / mebi,
             self.path_seg,
             count_str
-        );
+        )
+        .unwrap();
 
         for child in &self.children {
-            child.print(depth + 1);
+            child.print(depth + 1, output);
         }
     }
 }
@@ -349,7 +378,7 @@ impl ReportsForest {
         t.insert(tail, size);
     }
 
-    fn print(&mut self) {
+    fn print(&mut self, output: &mut String) {
         // Fill in sizes of interior nodes, and recursively sort the sub-trees.
         for (_, tree) in &mut self.trees {
             tree.compute_interior_node_sizes_and_sort();
@@ -374,10 +403,10 @@ impl ReportsForest {
 
         // Print the forest.
         for tree in &v {
-            tree.print(0);
+            tree.print(0, output);
             // Print a blank line after non-degenerate trees.
             if !tree.children.is_empty() {
-                println!("|");
+                writeln!(output, "|").unwrap();
             }
         }
     }
@@ -15,12 +15,12 @@ use profile_traits::time::{
     ProfilerCategory, ProfilerChan, ProfilerData, ProfilerMsg, TimerMetadata,
 };
 use profile_traits::time::{TimerMetadataFrameType, TimerMetadataReflowType};
-use servo_config::opts::OutputOptions;
+use servo_config::opts::{DbAuth, OutputOptions};
 use std::borrow::ToOwned;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Write};
 use std::path::Path;
 use std::time::Duration;
@@ -164,6 +164,17 @@ impl Formattable for ProfilerCategory {
 
 type ProfilerBuckets = BTreeMap<(ProfilerCategory, Option<TimerMetadata>), Vec<f64>>;
 
+#[derive(Debug, Serialize)]
+struct JsonLinesEntry<'a> {
+    category: ProfilerCategory,
+    metadata: &'a Option<TimerMetadata>,
+    mean: f64,
+    median: f64,
+    min: f64,
+    max: f64,
+    events: usize,
+}
+
 // back end of the profiler that handles data aggregation and performance metrics
 pub struct Profiler {
     pub port: IpcReceiver<ProfilerMsg>,
@@ -195,24 +206,18 @@ impl Profiler {
                         profiler.start();
                     })
                     .expect("Thread spawning failed");
-                // decide if we need to spawn the timer thread
-                match option {
-                    &OutputOptions::FileName(_) | &OutputOptions::DB(_, _, _, _) => {
-                        /* no timer thread needed */
-                    },
-                    &OutputOptions::Stdout(period) => {
-                        // Spawn a timer thread
-                        let chan = chan.clone();
-                        thread::Builder::new()
-                            .name("Time profiler timer".to_owned())
-                            .spawn(move || loop {
-                                thread::sleep(duration_from_seconds(period));
-                                if chan.send(ProfilerMsg::Print).is_err() {
-                                    break;
-                                }
-                            })
-                            .expect("Thread spawning failed");
-                    },
+                // Spawn a timer thread if the profile is printed periodically.
+                if let Some(period) = option.interval() {
+                    let chan = chan.clone();
+                    thread::Builder::new()
+                        .name("Time profiler timer".to_owned())
+                        .spawn(move || loop {
+                            thread::sleep(duration_from_seconds(period));
+                            if chan.send(ProfilerMsg::Print).is_err() {
+                                break;
+                            }
+                        })
+                        .expect("Thread spawning failed");
                 }
             },
             None => {
@@ -365,6 +370,10 @@ impl Profiler {
             ProfilerMsg::Exit(chan) => {
                 heartbeats::cleanup();
                 self.print_buckets();
+                if let Some(OutputOptions::StdoutAndFileName(_, ref filename)) = self.output {
+                    let output = Some(OutputOptions::FileName(filename.clone()));
+                    self.write_buckets(&output);
+                }
                 let _ = chan.send(());
                 return false;
             },
@@ -392,10 +401,21 @@ impl Profiler {
     }
 
     fn print_buckets(&mut self) {
-        match self.output {
+        let output = match self.output {
+            // Periodic summaries go to stdout, the file is only written on exit.
+            Some(OutputOptions::StdoutAndFileName(period, _)) => {
+                Some(OutputOptions::Stdout(period))
+            },
+            ref output => output.clone(),
+        };
+        self.write_buckets(&output);
+    }
+
+    fn write_buckets(&mut self, output: &Option<OutputOptions>) {
+        match *output {
             Some(OutputOptions::FileName(ref filename)) => {
                 let path = Path::new(&filename);
-                let mut file = match File::create(&path) {
+                let mut file = match create_output_file(&path) {
                     Err(e) => panic!(
                         "Couldn't create {}: {}",
                         path.display(),
@@ -417,8 +437,8 @@ impl Profiler {
                         write!(
                             file,
                             "{}\t{}\t{:15.4}\t{:15.4}\t{:15.4}\t{:15.4}\t{:15}\n",
-                            category.format(&self.output),
-                            meta.format(&self.output),
+                            category.format(output),
+                            meta.format(output),
                             mean,
                             median,
                             min,
@@ -434,6 +454,35 @@ impl Profiler {
                     write!(file, "{}\t{}\n", url, count).unwrap();
                 }
             },
+            Some(OutputOptions::JsonLines(ref filename)) => {
+                let path = Path::new(&filename);
+                let mut file = match create_output_file(&path) {
+                    Err(e) => panic!(
+                        "Couldn't create {}: {}",
+                        path.display(),
+                        Error::description(&e)
+                    ),
+                    Ok(file) => file,
+                };
+                for (&(ref category, ref meta), ref mut data) in &mut self.buckets {
+                    data.sort_by(|a, b| a.partial_cmp(b).expect("No NaN values in profiles"));
+                    let data_len = data.len();
+                    if data_len > 0 {
+                        let (mean, median, min, max) = Self::get_statistics(data);
+                        let entry = JsonLinesEntry {
+                            category: *category,
+                            metadata: meta,
+                            mean: mean,
+                            median: median,
+                            min: min,
+                            max: max,
+                            events: data_len,
+                        };
+                        serde_json::to_writer(&mut file, &entry).unwrap();
+                        writeln!(file).unwrap();
+                    }
+                }
+            },
             Some(OutputOptions::Stdout(_)) => {
                 let stdout = io::stdout();
                 let mut lock = stdout.lock();
@@ -460,8 +509,8 @@ impl Profiler {
                         writeln!(
                             &mut lock,
                             "{:-35}{} {:15.4} {:15.4} {:15.4} {:15.4} {:15}",
-                            category.format(&self.output),
-                            meta.format(&self.output),
+                            category.format(output),
+                            meta.format(output),
                             mean,
                             median,
                             min,
@@ -479,7 +528,7 @@ impl Profiler {
                 }
                 writeln!(&mut lock, "").unwrap();
             },
-            Some(OutputOptions::DB(ref hostname, ref dbname, ref user, ref password)) => {
+            Some(OutputOptions::DB(ref hostname, ref dbname, ref auth)) => {
                 // Unfortunately, influent does not like hostnames ending with "/"
                 let mut hostname = hostname.to_string();
                 if hostname.ends_with("/") {
@@ -487,8 +536,15 @@ impl Profiler {
                 }
 
                 let empty = String::from("");
-                let username = user.as_ref().unwrap_or(&empty);
-                let password = password.as_ref().unwrap_or(&empty);
+                let (username, password) = match *auth {
+                    DbAuth::UserPass(ref user, ref password) => (
+                        user.as_ref().unwrap_or(&empty),
+                        password.as_ref().unwrap_or(&empty),
+                    ),
+                    // InfluxDB 2 accepts a token as the password of its 1.x compatible API,
+                    // with any user name.
+                    DbAuth::Token(ref token) => (&empty, token),
+                };
                 let database = dbname.as_ref().unwrap_or(&empty);
                 let credentials = Credentials {
                     username: username,
@@ -504,7 +560,7 @@ impl Profiler {
                     let data_len = data.len();
                     if data_len > 0 {
                         let (mean, median, min, max) = Self::get_statistics(data);
-                        let category = category.format(&self.output);
+                        let category = category.format(output);
                         let mut measurement = Measurement::new(&category);
                         measurement.add_field("mean", Value::Float(mean));
                         measurement.add_field("median", Value::Float(median));
@@ -520,6 +576,8 @@ impl Profiler {
                     }
                 }
             },
+            // Combined output is split into its parts before reaching this point.
+            Some(OutputOptions::StdoutAndFileName(..)) => {},
             None => { /* Do nothing if no output option has been set */ },
         };
     }
@@ -539,6 +597,15 @@ where
     }
 }
 
+/// Create the file at `path` and any missing directories above it, which may be an
+/// `--output-dir` that doesn't exist yet.
+pub fn create_output_file(path: &Path) -> io::Result<File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    File::create(path)
+}
+
 pub fn duration_from_seconds(secs: f64) -> Duration {
     pub const NANOS_PER_SEC: u32 = 1_000_000_000;
 
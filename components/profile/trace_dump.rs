@@ -35,11 +35,14 @@ struct TraceEntry {
 
 impl TraceDump {
     /// Create a new TraceDump and write the prologue of the HTML file out to
-    /// disk.
+    /// disk, creating the file's directory if needed.
     pub fn new<P>(trace_file_path: P) -> io::Result<TraceDump>
     where
         P: AsRef<path::Path>,
     {
+        if let Some(dir) = trace_file_path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
         let mut file = fs::File::create(trace_file_path)?;
         write_prologue(&mut file)?;
         Ok(TraceDump { file: file })
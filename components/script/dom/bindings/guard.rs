@@ -48,7 +48,7 @@ pub enum Condition {
 impl Condition {
     unsafe fn is_satisfied(&self, cx: *mut JSContext, obj: HandleObject) -> bool {
         match *self {
-            Condition::Pref(name) => prefs::pref_map().get(name).as_bool().unwrap_or(false),
+            Condition::Pref(name) => prefs::pref_map().get_bool(name).unwrap_or(false),
             Condition::Func(f) => f(cx, obj),
             Condition::Satisfied => true,
         }
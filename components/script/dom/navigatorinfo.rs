@@ -3,6 +3,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
 use crate::dom::bindings::str::DOMString;
+use servo_config::opts;
 use std::borrow::Cow;
 
 pub fn Product() -> DOMString {
@@ -62,5 +63,5 @@ pub fn AppVersion() -> DOMString {
 }
 
 pub fn Language() -> DOMString {
-    DOMString::from("en-US")
+    DOMString::from(opts::get().effective_locale())
 }
@@ -14,37 +14,39 @@ use std::path::PathBuf;
 
 pub fn load_script(head: &HTMLHeadElement) {
     let doc = document_from_node(head);
-    let path_str = match doc.window().get_userscripts_path() {
-        Some(p) => p,
-        None => return,
-    };
+    let paths = doc.window().get_userscripts_paths();
+    if paths.is_empty() {
+        return;
+    }
     let win = Trusted::new(doc.window());
     doc.add_delayed_task(task!(UserScriptExecute: move || {
         let win = win.root();
         let cx = win.get_cx();
         rooted!(in(cx) let mut rval = UndefinedValue());
 
-        let path = PathBuf::from(&path_str);
-        let mut files = read_dir(&path)
-            .expect("Bad path passed to --userscripts")
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .collect::<Vec<_>>();
+        for path_str in &paths {
+            let path = PathBuf::from(path_str);
+            let mut files = read_dir(&path)
+                .expect("Bad path passed to --userscripts")
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .collect::<Vec<_>>();
 
-        files.sort();
+            files.sort();
 
-        for file in files {
-            let mut f = File::open(&file).unwrap();
-            let mut contents = vec![];
-            f.read_to_end(&mut contents).unwrap();
-            let script_text = String::from_utf8_lossy(&contents);
-            win.upcast::<GlobalScope>()
-                .evaluate_script_on_global_with_result(
-                    &script_text,
-                    &file.to_string_lossy(),
-                    rval.handle_mut(),
-                    1,
-                );
+            for file in files {
+                let mut f = File::open(&file).unwrap();
+                let mut contents = vec![];
+                f.read_to_end(&mut contents).unwrap();
+                let script_text = String::from_utf8_lossy(&contents);
+                win.upcast::<GlobalScope>()
+                    .evaluate_script_on_global_with_result(
+                        &script_text,
+                        &file.to_string_lossy(),
+                        rval.handle_mut(),
+                        1,
+                    );
+            }
         }
     }));
 }
@@ -309,10 +309,10 @@ pub struct Window {
     /// Unminify Javascript.
     unminify_js: bool,
 
-    /// Where to load userscripts from, if any. An empty string will load from
+    /// Directories to load userscripts from, in order. An empty string will load from
     /// the resources/user-agent-js directory, and if the option isn't passed userscripts
     /// won't be loaded.
-    userscripts_path: Option<String>,
+    userscripts_paths: Vec<String>,
 
     /// Replace unpaired surrogates in DOM strings with U+FFFD.
     /// See <https://github.com/servo/servo/issues/6564>
@@ -469,8 +469,8 @@ impl Window {
         self.webrender_api_sender.clone()
     }
 
-    pub fn get_userscripts_path(&self) -> Option<String> {
-        self.userscripts_path.clone()
+    pub fn get_userscripts_paths(&self) -> Vec<String> {
+        self.userscripts_paths.clone()
     }
 
     pub fn replace_surrogates(&self) -> bool {
@@ -2072,7 +2072,7 @@ impl Window {
         relayout_event: bool,
         prepare_for_screenshot: bool,
         unminify_js: bool,
-        userscripts_path: Option<String>,
+        userscripts_paths: Vec<String>,
         is_headless: bool,
         replace_surrogates: bool,
         user_agent: Cow<'static, str>,
@@ -2154,7 +2154,7 @@ impl Window {
             relayout_event,
             prepare_for_screenshot,
             unminify_js,
-            userscripts_path,
+            userscripts_paths,
             replace_surrogates,
         });
 
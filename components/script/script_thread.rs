@@ -660,10 +660,10 @@ pub struct ScriptThread {
     /// Unminify Javascript.
     unminify_js: bool,
 
-    /// Where to load userscripts from, if any. An empty string will load from
+    /// Directories to load userscripts from, in order. An empty string will load from
     /// the resources/user-agent-js directory, and if the option isn't passed userscripts
     /// won't be loaded
-    userscripts_path: Option<String>,
+    userscripts_paths: Vec<String>,
 
     /// True if headless mode.
     headless: bool,
@@ -716,7 +716,7 @@ impl ScriptThreadFactory for ScriptThread {
         relayout_event: bool,
         prepare_for_screenshot: bool,
         unminify_js: bool,
-        userscripts_path: Option<String>,
+        userscripts_paths: Vec<String>,
         headless: bool,
         replace_surrogates: bool,
         user_agent: Cow<'static, str>,
@@ -751,7 +751,7 @@ impl ScriptThreadFactory for ScriptThread {
                     relayout_event,
                     prepare_for_screenshot,
                     unminify_js,
-                    userscripts_path,
+                    userscripts_paths,
                     headless,
                     replace_surrogates,
                     user_agent,
@@ -1115,7 +1115,7 @@ impl ScriptThread {
         relayout_event: bool,
         prepare_for_screenshot: bool,
         unminify_js: bool,
-        userscripts_path: Option<String>,
+        userscripts_paths: Vec<String>,
         headless: bool,
         replace_surrogates: bool,
         user_agent: Cow<'static, str>,
@@ -1230,7 +1230,7 @@ impl ScriptThread {
             prepare_for_screenshot,
             unminify_js,
 
-            userscripts_path,
+            userscripts_paths,
             headless,
             replace_surrogates,
             user_agent,
@@ -2975,7 +2975,7 @@ impl ScriptThread {
             self.relayout_event,
             self.prepare_for_screenshot,
             self.unminify_js,
-            self.userscripts_path.clone(),
+            self.userscripts_paths.clone(),
             self.headless,
             self.replace_surrogates,
             self.user_agent.clone(),
@@ -650,7 +650,7 @@ pub trait ScriptThreadFactory {
         relayout_event: bool,
         prepare_for_screenshot: bool,
         unminify_js: bool,
-        userscripts_path: Option<String>,
+        userscripts_paths: Vec<String>,
         headless: bool,
         replace_surrogates: bool,
         user_agent: Cow<'static, str>,
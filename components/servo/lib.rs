@@ -95,16 +95,18 @@ use ipc_channel::ipc::{self, IpcSender};
 use log::{Log, Metadata, Record};
 use msg::constellation_msg::{PipelineNamespace, PipelineNamespaceId};
 use net::resource_thread::new_resource_threads;
-use net_traits::IpcSend;
+use net_traits::request::{Destination, RequestBuilder};
+use net_traits::{CoreResourceMsg, CoreResourceThread, FetchChannels, FetchMetadata};
+use net_traits::{FetchResponseMsg, IpcSend};
 use profile::mem as profile_mem;
 use profile::time as profile_time;
 use profile_traits::mem;
 use profile_traits::time;
 use script_traits::{ConstellationMsg, SWManagerSenders, ScriptToConstellationChan};
-use servo_config::opts;
+use servo_config::opts::{self, NetworkingConfig};
 use servo_config::{pref, prefs};
 use servo_media::ServoMedia;
-use std::borrow::Cow;
+use servo_url::ServoUrl;
 use std::cmp::max;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -226,12 +228,16 @@ where
             opts.time_profiler_trace_path.clone(),
             opts.profile_heartbeats,
         );
-        let mem_profiler_chan = profile_mem::Profiler::create(opts.mem_profiler_period);
+        let mem_profiler_chan = profile_mem::Profiler::create(opts.mem_profiler_output.clone());
         let debugger_chan = opts.debugger_port.map(|port| debugger::start_server(port));
         let devtools_chan = opts.devtools_port.map(|port| devtools::start_server(port));
 
         let coordinates = window.get_coordinates();
 
+        if opts.force_webrender {
+            info!("WebRender requested with -w; it is always used.");
+        }
+
         let (mut webrender, webrender_api_sender) = {
             let renderer_kind = if opts::get().should_use_osmesa() {
                 RendererKind::OSMesa
@@ -299,7 +305,7 @@ where
         // pipelines, including the script and layout threads, as well
         // as the navigation context.
         let (constellation_chan, sw_senders) = create_constellation(
-            opts.user_agent.clone(),
+            opts.clone_networking_config(),
             opts.config_dir.clone(),
             embedder_proxy.clone(),
             compositor_proxy.clone(),
@@ -339,10 +345,14 @@ where
                 webvr_heartbeats,
             },
             opts.output_file.clone(),
+            opts.output_format,
+            opts.output_color_space,
+            opts.background_color,
             opts.is_running_problem_test,
             opts.exit_after_load,
             opts.convert_mouse_to_touch,
             opts.device_pixels_per_px,
+            opts.initial_zoom,
         );
 
         Servo {
@@ -565,11 +575,12 @@ where
 
     pub fn setup_logging(&self) {
         let constellation_chan = self.constellation_chan.clone();
-        let env = env_logger::Env::default();
-        let env_logger = EnvLoggerBuilder::from_env(env).build();
+        let env_logger = env_logger_builder().build();
         let con_logger = FromCompositorLogger::new(constellation_chan);
 
-        let filter = max(env_logger.filter(), con_logger.filter());
+        let filter = opts::get()
+            .log_level
+            .unwrap_or_else(|| max(env_logger.filter(), con_logger.filter()));
         let logger = BothLogger(env_logger, con_logger);
 
         log::set_boxed_logger(Box::new(logger)).expect("Failed to set logger.");
@@ -608,7 +619,7 @@ fn create_compositor_channel(
 }
 
 fn create_constellation(
-    user_agent: Cow<'static, str>,
+    networking_config: NetworkingConfig,
     config_dir: Option<PathBuf>,
     embedder_proxy: EmbedderProxy,
     compositor_proxy: CompositorProxy,
@@ -622,21 +633,23 @@ fn create_constellation(
     window_gl: Rc<dyn gl::Gl>,
     webvr_services: Option<VRServiceManager>,
 ) -> (Sender<ConstellationMsg>, SWManagerSenders) {
-    // Global configuration options, parsed from the command line.
-    let opts = opts::get();
-
     let bluetooth_thread: IpcSender<BluetoothRequest> =
         BluetoothThreadFactory::new(embedder_proxy.clone());
 
     let (public_resource_threads, private_resource_threads) = new_resource_threads(
-        user_agent,
+        networking_config,
         devtools_chan.clone(),
         time_profiler_chan.clone(),
         mem_profiler_chan.clone(),
         embedder_proxy.clone(),
         config_dir,
-        opts.certificate_path.clone(),
     );
+
+    // This updates the global options, so it has to happen before they are locked below.
+    fetch_user_stylesheets(&public_resource_threads.sender());
+
+    // Global configuration options, parsed from the command line.
+    let opts = opts::get();
     let font_cache_thread = FontCacheThread::new(
         public_resource_threads.sender(),
         webrender_api_sender.create_api(),
@@ -708,12 +721,14 @@ fn create_constellation(
         script::script_thread::ScriptThread,
     >::start(
         initial_state,
-        opts.initial_window_size,
+        opts.initial_window_size.size,
         opts.device_pixels_per_px,
         opts.random_pipeline_closure_probability,
         opts.random_pipeline_closure_seed,
         opts.is_running_problem_test,
         opts.hard_fail,
+        opts.failure_url.clone(),
+        opts.exit_after_navigations,
         opts.enable_canvas_antialiasing,
     );
 
@@ -733,6 +748,64 @@ fn create_constellation(
     (constellation_chan, sw_senders)
 }
 
+/// Fetch the http(s) stylesheets given to `--user-stylesheet` and add them to
+/// `Opts::user_stylesheets`, after the local ones. This must run before the first layout thread
+/// starts, since layout reads the user stylesheets only once. A stylesheet that can't be fetched
+/// is skipped, like a local one that can't be read.
+fn fetch_user_stylesheets(core_resource_thread: &CoreResourceThread) {
+    let mut opts = opts::get_cloned();
+    if opts.user_stylesheet_urls.is_empty() {
+        return;
+    }
+
+    for url in &opts.user_stylesheet_urls {
+        match fetch_user_stylesheet(url, core_resource_thread) {
+            Ok(contents) => opts.user_stylesheets.push((contents, url.clone())),
+            Err(error) => warn!("Skipping user stylesheet {}: {}", url, error),
+        }
+    }
+    opts::set_options(opts);
+}
+
+fn fetch_user_stylesheet(
+    url: &ServoUrl,
+    core_resource_thread: &CoreResourceThread,
+) -> Result<Vec<u8>, String> {
+    let (action_sender, action_receiver) = ipc::channel().unwrap();
+    let request = RequestBuilder::new(url.clone()).destination(Destination::Style);
+    core_resource_thread
+        .send(CoreResourceMsg::Fetch(
+            request,
+            FetchChannels::ResponseMsg(action_sender, None),
+        ))
+        .unwrap();
+
+    let mut contents = vec![];
+    loop {
+        match action_receiver.recv().unwrap() {
+            FetchResponseMsg::ProcessRequestBody | FetchResponseMsg::ProcessRequestEOF => (),
+            FetchResponseMsg::ProcessResponse(Ok(metadata)) => {
+                let metadata = match metadata {
+                    FetchMetadata::Unfiltered(metadata) => metadata,
+                    FetchMetadata::Filtered { unsafe_, .. } => unsafe_,
+                };
+                match metadata.status {
+                    Some((status, _)) if status < 200 || status >= 300 => {
+                        return Err(format!("HTTP status {}", status));
+                    },
+                    _ => {},
+                }
+            },
+            FetchResponseMsg::ProcessResponseChunk(data) => contents.extend_from_slice(&data),
+            FetchResponseMsg::ProcessResponseEOF(Ok(_)) => return Ok(contents),
+            FetchResponseMsg::ProcessResponse(Err(error)) |
+            FetchResponseMsg::ProcessResponseEOF(Err(error)) => {
+                return Err(format!("{:?}", error));
+            },
+        }
+    }
+}
+
 // A logger that logs to two downstream loggers.
 // This should probably be in the log crate.
 struct BothLogger<Log1, Log2>(Log1, Log2);
@@ -757,12 +830,23 @@ where
     }
 }
 
+/// An `env_logger` configured from `RUST_LOG`, unless `--log-level` was given.
+fn env_logger_builder() -> EnvLoggerBuilder {
+    let env = env_logger::Env::default();
+    let mut builder = EnvLoggerBuilder::from_env(env);
+    if let Some(level) = opts::get().log_level {
+        builder.filter_level(level);
+    }
+    builder
+}
+
 pub fn set_logger(script_to_constellation_chan: ScriptToConstellationChan) {
     let con_logger = FromScriptLogger::new(script_to_constellation_chan);
-    let env = env_logger::Env::default();
-    let env_logger = EnvLoggerBuilder::from_env(env).build();
+    let env_logger = env_logger_builder().build();
 
-    let filter = max(env_logger.filter(), con_logger.filter());
+    let filter = opts::get()
+        .log_level
+        .unwrap_or_else(|| max(env_logger.filter(), con_logger.filter()));
     let logger = BothLogger(env_logger, con_logger);
 
     log::set_boxed_logger(Box::new(logger)).expect("Failed to set logger.");
@@ -780,7 +864,11 @@ pub fn run_content_process(token: String) {
         .unwrap();
 
     let mut unprivileged_content = unprivileged_content_receiver.recv().unwrap();
-    opts::set_options(unprivileged_content.opts());
+    opts::set_options(
+        unprivileged_content
+            .opts()
+            .expect("Failed to read the pipeline options"),
+    );
     prefs::pref_map()
         .set_all(unprivileged_content.prefs())
         .expect("Failed to set preferences");
@@ -516,7 +516,7 @@ impl NonCustomPropertyId {
                     Some(pref) => pref,
                 };
 
-                prefs::pref_map().get(pref).as_bool().unwrap_or(false)
+                prefs::pref_map().get_bool(pref).unwrap_or(false)
             % else:
                 unsafe { structs::nsCSSProps_gPropertyEnabled[self.0] }
             % endif
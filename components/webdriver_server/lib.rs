@@ -241,6 +241,13 @@ impl Serialize for WebDriverPrefValue {
             PrefValue::Str(ref s) => serializer.serialize_str(&s),
             PrefValue::Float(f) => serializer.serialize_f64(f),
             PrefValue::Int(i) => serializer.serialize_i64(i),
+            PrefValue::Array(ref values) => {
+                serializer.collect_seq(values.iter().map(|value| WebDriverPrefValue(value.clone())))
+            },
+            PrefValue::Object(ref map) => serializer.collect_map(
+                map.iter()
+                    .map(|(key, value)| (key, WebDriverPrefValue(value.clone()))),
+            ),
             PrefValue::Missing => serializer.serialize_unit(),
         }
     }
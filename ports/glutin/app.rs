@@ -10,7 +10,7 @@ use crate::window_trait::WindowPortsMethods;
 use crate::events_loop::EventsLoop;
 use crate::{headed_window, headless_window};
 use servo::compositing::windowing::WindowEvent;
-use servo::config::opts::{self, parse_url_or_filename};
+use servo::config::opts::{self, parse_url_or_filename, GraphicsBackend};
 use servo::servo_config::pref;
 use servo::servo_url::ServoUrl;
 use servo::{BrowserId, Servo};
@@ -34,9 +34,9 @@ impl App {
 
         // Implements window methods, used by compositor.
         let window = if opts::get().headless {
-            headless_window::Window::new(opts::get().initial_window_size)
+            headless_window::Window::new(opts::get().initial_window_size.size)
         } else {
-            headed_window::Window::new(opts::get().initial_window_size, events_loop.borrow().as_winit())
+            headed_window::Window::new(opts::get().initial_window_size.size, events_loop.borrow().as_winit())
         };
 
         // Implements embedder methods, used by libservo and constellation.
@@ -185,7 +185,8 @@ fn get_default_url() -> ServoUrl {
 }
 
 pub fn gl_version() -> glutin::GlRequest {
-    if opts::get().angle {
+    let opts = opts::get();
+    if opts.angle || opts.graphics_backend == GraphicsBackend::Es2 {
         glutin::GlRequest::Specific(glutin::Api::OpenGlEs, (3, 0))
     } else {
         glutin::GlRequest::GlThenGles {
@@ -44,7 +44,7 @@ impl EmbedderMethods for EmbedderCallbacks {
                 warn!("Creating test VR display");
                 // This is safe, because register_vr_services is called from the main thread.
                 let name = String::from("Test VR Display");
-                let size = opts::get().initial_window_size.to_f64();
+                let size = opts::get().initial_window_size.size.to_f64();
                 let size = LogicalSize::new(size.width, size.height);
                 let window_builder = glutin::WindowBuilder::new()
                     .with_title(name.clone())
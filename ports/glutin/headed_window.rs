@@ -119,6 +119,12 @@ impl Window {
             .build_windowed(window_builder, &events_loop)
             .expect("Failed to create window.");
 
+        if let Some(position) = opts.initial_window_position {
+            context
+                .window()
+                .set_position(LogicalPosition::new(position.x as f64, position.y as f64));
+        }
+
         #[cfg(any(target_os = "linux", target_os = "windows"))]
         {
             let icon_bytes = include_bytes!("../../resources/servo64.png");
@@ -180,6 +186,10 @@ impl Window {
 
         window.present();
 
+        if opts.fullscreen {
+            window.set_fullscreen(true);
+        }
+
         Rc::new(window)
     }
 
@@ -33,12 +33,60 @@ use std::thread;
 
 pub mod platform {
     #[cfg(target_os = "macos")]
-    pub use crate::platform::macos::deinit;
+    pub use crate::platform::macos::running_thread_count;
+
+    #[cfg(target_os = "linux")]
+    pub use crate::platform::linux::running_thread_count;
+
+    #[cfg(target_os = "windows")]
+    pub use crate::platform::windows::running_thread_count;
 
     #[cfg(target_os = "macos")]
     pub mod macos;
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    pub mod linux;
+
+    #[cfg(target_os = "windows")]
+    pub mod windows;
+
+    #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+    pub fn deinit() {
+        use servo::config::opts;
+        use std::thread;
+        use std::time::Duration;
+
+        #[cfg(target_os = "macos")]
+        macos::deinit();
+
+        // Without --clean-shutdown, leftover threads are only reported on macOS.
+        let clean_shutdown = opts::get().clean_shutdown;
+        if !clean_shutdown && !cfg!(target_os = "macos") {
+            return;
+        }
+
+        let thread_count = running_thread_count();
+        if thread_count <= 1 {
+            debug!("All threads have shutdown (good).");
+            return;
+        }
+        warn!("{} threads are still running after shutdown (bad).", thread_count);
+        if !clean_shutdown {
+            return;
+        }
+
+        debug!("Waiting until all threads have shutdown");
+        loop {
+            let thread_count = running_thread_count();
+            if thread_count <= 1 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1000));
+            debug!("{} threads are still running.", thread_count);
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     pub fn deinit() {}
 }
 
@@ -0,0 +1,13 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::fs;
+
+/// The number of threads running in this process.
+pub fn running_thread_count() -> usize {
+    // Every thread of this process has an entry in /proc/self/task.
+    fs::read_dir("/proc/self/task")
+        .map(|entries| entries.count())
+        .unwrap_or(1)
+}
@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use std::mem;
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::um::processthreadsapi::GetCurrentProcessId;
+use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Thread32First, Thread32Next};
+use winapi::um::tlhelp32::{TH32CS_SNAPTHREAD, THREADENTRY32};
+
+/// The number of threads running in this process.
+pub fn running_thread_count() -> usize {
+    unsafe {
+        // The snapshot covers every thread in the system, so only count our own.
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return 1;
+        }
+        let process_id = GetCurrentProcessId();
+        let mut entry: THREADENTRY32 = mem::zeroed();
+        entry.dwSize = mem::size_of::<THREADENTRY32>() as u32;
+        let mut count = 0;
+        let mut has_entry = Thread32First(snapshot, &mut entry) != 0;
+        while has_entry {
+            if entry.th32OwnerProcessID == process_id {
+                count += 1;
+            }
+            has_entry = Thread32Next(snapshot, &mut entry) != 0;
+        }
+        CloseHandle(snapshot);
+        count
+    }
+}